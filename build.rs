@@ -12,12 +12,10 @@ fn main() {
         let dir_path = format!("examples/test/{}", category);
         if let Ok(entries) = fs::read_dir(&dir_path) {
             let mut paths = Vec::new();
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_file() && path.extension().map_or(false, |ext| ext == "ns") {
-                        paths.push(path);
-                    }
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "ns") {
+                    paths.push(path);
                 }
             }
             paths.sort();
@@ -25,6 +23,7 @@ fn main() {
             for path in paths {
                 let file_name = path.file_stem().unwrap().to_str().unwrap();
                 let clean_name = format!("{}_{}", category, file_name)
+                    .to_lowercase()
                     .replace(".", "_")
                     .replace("-", "_")
                     .replace(" ", "_");
@@ -20,7 +20,7 @@ fn main() {
     io::stdin().read_line(&mut lesson_choice).unwrap();
     
     let lesson_num: u32 = match lesson_choice.trim().parse() {
-        Ok(num) if num >= 1 && num <= 10 => num,
+        Ok(num) if (1..=10).contains(&num) => num,
         _ => return,
     };
 
@@ -31,6 +31,6 @@ fn main() {
     }
 
     let _status = Command::new("cargo")
-        .args(&["run", "--quiet", "--", "build", &relative_path])
+        .args(["run", "--quiet", "--", "build", &relative_path])
         .status();
 }
@@ -1,34 +1,190 @@
+use std::sync::Arc;
+
+/// Parametri di una funzione o campi di un record, con il valore predefinito facoltativo.
+pub type Params = Vec<(String, Option<Expression>)>;
+
+/// Corpo di un blocco: condiviso (e non copiato) da chi lo riprende più volte, come i generatori.
+/// `Arc` perché le funzioni del programma vengono passate ai task.
+pub type Block = Arc<[Statement]>;
+
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum Statement {
-    VarDecl { is_mutable: bool, name: String, value: Expression },
-    Assignment { target: Expression, value: Expression },
-    IfStatement { condition: Expression, then_branch: Vec<Statement>, else_branch: Option<Vec<Statement>> },
-    WhileStatement { condition: Expression, body: Vec<Statement> },
-    ForStatement { iterator: String, start: Expression, end: Expression, body: Vec<Statement> },
-    SwitchStatement { discriminant: Expression, cases: Vec<(Expression, Vec<Statement>)>, default_case: Option<Vec<Statement>> },
-    ReturnStatement { value: Expression },
-    FunctionDecl { name: String, params: Vec<String>, body: Vec<Statement> },
-    TryCatchStatement { try_block: Vec<Statement>, catch_variable: Option<String>, catch_block: Option<Vec<Statement>>, finally_block: Option<Vec<Statement>> },
-    ThrowStatement { value: Expression },
-    Break,
-    Continue,
-    Expr(Expression),
+    VarDecl { is_mutable: bool, name: String, value: Expression, line: usize },
+    // `let [a, ...resto] = valore` / `let {nome, eta} = valore`: dichiara le variabili legate dal pattern
+    PatternDecl { is_mutable: bool, pattern: Pattern, value: Expression, line: usize },
+    Assignment { target: Expression, value: Expression, line: usize },
+    // `a, b = b, a`: i valori sono calcolati tutti prima di assegnarli
+    MultiAssignment { targets: Vec<Expression>, values: Vec<Expression>, line: usize },
+    IfStatement { condition: Expression, then_branch: Block, else_branch: Option<Block>, line: usize },
+    // `label` è l'etichetta facoltativa del ciclo (`esterno: mentre ...`), usata da break e continue.
+    // Anche `loop { }` è un WhileStatement, con la condizione sempre vera
+    WhileStatement { condition: Expression, body: Block, label: Option<String>, line: usize },
+    // `do { } while (c)`; `repeat { } until (c)` ha la condizione negata. Il corpo è eseguito almeno una volta
+    DoWhileStatement { body: Block, condition: Expression, label: Option<String>, line: usize },
+    // Senza `end` il ciclo visita gli elementi della collezione `start`, altrimenti l'intervallo `start..end`
+    ForStatement { iterator: String, start: Expression, end: Option<Expression>, body: Block, label: Option<String>, line: usize },
+    SwitchStatement { discriminant: Expression, cases: Vec<(Expression, Block)>, default_case: Option<Block>, line: usize },
+    ReturnStatement { value: Expression, line: usize },
+    // `rest` raccoglie in un array gli argomenti in più; una funzione che contiene `yield` è un generatore
+    FunctionDecl { name: String, params: Params, rest: Option<String>, body: Block, is_generator: bool, line: usize },
+    // Campi nell'ordine di dichiarazione, con il valore predefinito facoltativo
+    RecordDecl { name: String, fields: Params, line: usize },
+    TryCatchStatement { try_block: Block, catch_variable: Option<String>, catch_block: Option<Block>, finally_block: Option<Block>, line: usize },
+    ThrowStatement { value: Expression, line: usize },
+    // Sospende il generatore consegnando `value` a chi chiede il valore successivo
+    Yield { value: Expression, line: usize },
+    // Senza etichetta agiscono sul ciclo più interno
+    Break { label: Option<String>, line: usize },
+    Continue { label: Option<String>, line: usize },
+    Expr { expr: Expression, line: usize },
+}
+
+impl Statement {
+    /// Riga del sorgente in cui inizia lo statement.
+    pub fn line(&self) -> usize {
+        match self {
+            Statement::VarDecl { line, .. }
+            | Statement::PatternDecl { line, .. }
+            | Statement::Assignment { line, .. }
+            | Statement::MultiAssignment { line, .. }
+            | Statement::IfStatement { line, .. }
+            | Statement::WhileStatement { line, .. }
+            | Statement::DoWhileStatement { line, .. }
+            | Statement::ForStatement { line, .. }
+            | Statement::SwitchStatement { line, .. }
+            | Statement::ReturnStatement { line, .. }
+            | Statement::FunctionDecl { line, .. }
+            | Statement::RecordDecl { line, .. }
+            | Statement::TryCatchStatement { line, .. }
+            | Statement::ThrowStatement { line, .. }
+            | Statement::Yield { line, .. }
+            | Statement::Break { line, .. }
+            | Statement::Continue { line, .. }
+            | Statement::Expr { line, .. } => *line,
+        }
+    }
+
+    /// Vero se lo statement contiene un `yield`, escluso quello delle funzioni dichiarate al suo interno.
+    pub fn yields(&self) -> bool {
+        let any = |stmts: &[Statement]| stmts.iter().any(Statement::yields);
+        match self {
+            Statement::Yield { .. } => true,
+            Statement::IfStatement { then_branch, else_branch, .. } => any(then_branch) || else_branch.as_deref().is_some_and(any),
+            Statement::WhileStatement { body, .. } | Statement::DoWhileStatement { body, .. } | Statement::ForStatement { body, .. } => any(body),
+            Statement::SwitchStatement { cases, default_case, .. } => {
+                cases.iter().any(|(_, body)| any(body)) || default_case.as_deref().is_some_and(any)
+            }
+            Statement::TryCatchStatement { try_block, catch_block, finally_block, .. } => {
+                any(try_block) || catch_block.as_deref().is_some_and(any) || finally_block.as_deref().is_some_and(any)
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Expression {
     LiteralStr(String),
-    LiteralNum(f64),
+    // Stringa con interpolazioni: parti di testo ed espressioni concatenate nell'ordine
+    Template(Vec<Expression>),
+    LiteralInt(i64),
+    LiteralBigInt(num_bigint::BigInt),
+    LiteralFloat(f64),
     LiteralBool(bool),
     LiteralNull,
     Array(Vec<Expression>),
     Map(Vec<(String, Expression)>),
+    Set(Vec<Expression>),
     Index { target: Box<Expression>, index: Box<Expression> },
+    // `a?.b` / `a?.[i]`: vale null se `a` è null o se la chiave o l'indice mancano
+    OptionalIndex { target: Box<Expression>, index: Box<Expression> },
     Variable(String),
     BinaryOp { left: Box<Expression>, operator: String, right: Box<Expression> },
     UnaryOp { operator: String, operand: Box<Expression> },
     Ternary { condition: Box<Expression>, true_expr: Box<Expression>, false_expr: Box<Expression> },
     FunctionCall { target: Box<Expression>, args: Vec<Expression> },
+    // `valore.metodo(argomenti)`: chiama `metodo` con il valore come primo argomento
+    MethodCall { receiver: Box<Expression>, method: String, args: Vec<Expression> },
+    // Argomento con nome `parametro = valore`, ammesso solo nella lista degli argomenti di una chiamata
+    NamedArg { name: String, value: Box<Expression> },
+    // Valuta il primo ramo il cui pattern corrisponde a `subject` (altrimenti il ramo `default`)
+    Match { subject: Box<Expression>, arms: Vec<MatchArm>, default: Option<Box<Expression>> },
+}
+
+/// Ramo di un `match`: pattern, guardia facoltativa (`case x if x > 0`) e valore del ramo.
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expression>,
+    pub value: Expression,
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    // `_`: corrisponde a qualsiasi valore senza legarlo
+    Wildcard,
+    Binding(String),
+    // Valore confrontato con le regole di `==`
+    Literal(Expression),
+    // Intervallo `start..end`, con l'estremo finale escluso come nei cicli `for`
+    Range { start: Expression, end: Expression },
+    Alternatives(Vec<Pattern>),
+    // `[a, b, ...resto]`: senza `rest` la lunghezza dell'array deve coincidere
+    Array { items: Vec<Pattern>, rest: Option<Box<Pattern>> },
+    // `{"chiave": pattern, nome}` (anche sui campi di un record): le chiavi non elencate sono ignorate
+    Map(Vec<(String, Pattern)>),
+    // `Punto(x, y)`: campi di un record nell'ordine di dichiarazione
+    Record { name: String, fields: Vec<Pattern> },
+    // `integer`, `string(s)`, ...: corrisponde ai valori del tipo indicato
+    Type { name: String, inner: Option<Box<Pattern>> },
+}
+
+impl Pattern {
+    /// Variabili legate dal pattern, nell'ordine in cui compaiono.
+    pub fn bindings(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_bindings(&mut names);
+        names
+    }
+
+    fn collect_bindings<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Pattern::Binding(name) => names.push(name),
+            // Le alternative legano le stesse variabili: basta la prima
+            Pattern::Alternatives(options) => {
+                if let Some(first) = options.first() {
+                    first.collect_bindings(names);
+                }
+            }
+            Pattern::Array { items, rest } => {
+                for item in items.iter().chain(rest.as_deref()) {
+                    item.collect_bindings(names);
+                }
+            }
+            Pattern::Map(entries) => {
+                for (_, item) in entries {
+                    item.collect_bindings(names);
+                }
+            }
+            Pattern::Record { fields, .. } => {
+                for field in fields {
+                    field.collect_bindings(names);
+                }
+            }
+            Pattern::Type { inner: Some(inner), .. } => inner.collect_bindings(names),
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Range { .. } | Pattern::Type { inner: None, .. } => {}
+        }
+    }
+
+    /// Vero se il pattern corrisponde a qualsiasi valore.
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Pattern::Wildcard | Pattern::Binding(_) => true,
+            Pattern::Alternatives(options) => options.iter().any(|p| p.is_irrefutable()),
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
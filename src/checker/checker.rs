@@ -0,0 +1,879 @@
+use std::collections::HashMap;
+use crate::engine::ast::{Expression, MatchArm, Pattern, Program, Statement};
+use crate::engine::interpreter::builtins;
+use crate::engine::suggest::Suggestions;
+
+/// Variabile dichiarata in uno scope durante l'analisi statica.
+struct Binding {
+    is_mutable: bool,
+    line: usize,
+    used: bool,
+    // Solo le variabili dichiarate con let/const producono il warning di variabile inutilizzata
+    warn_unused: bool,
+}
+
+/// Funzione o record dichiarato nel programma, con il numero di argomenti accettati.
+struct Signature {
+    min_args: usize,
+    max_args: usize,
+    line: usize,
+    // Il nome di un record è il suo costruttore
+    is_record: bool,
+    // Nomi dei parametri (o dei campi) e se hanno un valore predefinito, per gli argomenti con nome
+    params: Vec<(String, bool)>,
+}
+
+/// Scope lessicale dell'analisi, speculare a quelli creati dall'interprete a runtime.
+#[derive(Default)]
+struct Scope<'a> {
+    vars: HashMap<String, Binding>,
+    // Corpi di funzione analizzati alla chiusura dello scope, quando tutte le dichiarazioni sono note
+    pending: Vec<&'a Statement>,
+}
+
+/// Analizzatore semantico eseguito tra il parsing e l'esecuzione.
+///
+/// Segnala come errori le variabili e le funzioni non definite, le chiamate con un numero errato
+/// di argomenti, le assegnazioni a costanti ed i `return` fuori dalle funzioni.
+/// Segnala come warning le variabili inutilizzate, lo shadowing ed il codice irraggiungibile.
+pub struct Checker<'a> {
+    scopes: Vec<Scope<'a>>,
+    // Come nell'interprete, funzioni e record vivono in una tabella globale
+    functions: HashMap<String, Signature>,
+    // Indici degli scope in cui inizia il corpo di ciascuna funzione in analisi
+    fn_scope_starts: Vec<usize>,
+    found_errors: Vec<(usize, String)>,
+    found_warnings: Vec<(usize, String)>,
+    // Suggerimenti "did you mean" per i nomi non definiti
+    suggestions: Suggestions<'a>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl<'a> Checker<'a> {
+    pub fn new(suggestions: Suggestions<'a>) -> Self {
+        Self {
+            suggestions,
+            scopes: Vec::new(),
+            functions: HashMap::new(),
+            fn_scope_starts: Vec::new(),
+            found_errors: Vec::new(),
+            found_warnings: Vec::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Analizza l'intero programma, popolando `errors` e `warnings` ordinati per riga.
+    pub fn check(&mut self, program: &'a Program) {
+        self.scopes = vec![Scope::default()];
+        self.functions.clear();
+        self.collect_functions(&program.statements);
+        self.check_block(&program.statements);
+        self.exit_scope();
+
+        self.found_errors.sort_by_key(|(line, _)| *line);
+        self.found_warnings.sort_by_key(|(line, _)| *line);
+        self.errors = self.found_errors.drain(..).map(|(_, msg)| msg).collect();
+        self.warnings = self.found_warnings.drain(..).map(|(_, msg)| msg).collect();
+    }
+
+    fn error(&mut self, line: usize, msg: String) {
+        self.found_errors.push((line, format!("Semantic Error (Line {}): {}", line, msg)));
+    }
+
+    fn warning(&mut self, line: usize, msg: String) {
+        self.found_warnings.push((line, format!("Warning (Line {}): {}", line, msg)));
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(Scope::default());
+    }
+
+    /// Chiude lo scope corrente: analizza i corpi di funzione in sospeso e segnala le variabili inutilizzate.
+    fn exit_scope(&mut self) {
+        let pending = match self.scopes.last_mut() {
+            Some(scope) => std::mem::take(&mut scope.pending),
+            None => return,
+        };
+        for func in pending {
+            self.check_function(func);
+        }
+
+        if let Some(scope) = self.scopes.pop() {
+            // Una destrutturazione dichiara più variabili sulla stessa riga: l'ordine dei warning segue il nome
+            let mut unused: Vec<(String, Binding)> = scope.vars.into_iter()
+                .filter(|(name, binding)| binding.warn_unused && !binding.used && !name.starts_with('_'))
+                .collect();
+            unused.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (name, binding) in unused {
+                self.warning(binding.line, format!("Variable '{}' is declared but never used", name));
+            }
+        }
+    }
+
+    /// Cerca lo scope che contiene la variabile, con le stesse regole di visibilità dell'interprete:
+    /// gli scope della funzione corrente e poi lo scope globale.
+    fn find_var(&self, name: &str) -> Option<usize> {
+        let start_idx = self.fn_scope_starts.last().copied().unwrap_or(0);
+        for idx in (start_idx..self.scopes.len()).rev() {
+            if self.scopes[idx].vars.contains_key(name) {
+                return Some(idx);
+            }
+        }
+        if start_idx > 0 && self.scopes[0].vars.contains_key(name) {
+            return Some(0);
+        }
+        None
+    }
+
+    /// Marca una variabile come utilizzata. Restituisce `false` se non è visibile.
+    fn use_var(&mut self, name: &str) -> bool {
+        match self.find_var(name) {
+            Some(idx) => {
+                if let Some(binding) = self.scopes[idx].vars.get_mut(name) {
+                    binding.used = true;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Suggerimento da aggiungere all'errore per un nome non definito (keyword di un'altra lingua
+    /// o nome simile tra variabili visibili, funzioni, built-in e keyword attive).
+    fn name_hint(&self, name: &str) -> String {
+        let start_idx = self.fn_scope_starts.last().copied().unwrap_or(0);
+        let mut visible: Vec<&str> = self.scopes[start_idx..]
+            .iter()
+            .chain(self.scopes.first().filter(|_| start_idx > 0))
+            .flat_map(|scope| scope.vars.keys().map(|k| k.as_str()))
+            .collect();
+        visible.extend(self.functions.keys().map(|k| k.as_str()));
+        visible.sort();
+        match self.suggestions.for_name(name, visible.into_iter()) {
+            Some(hint) => format!("; {}", hint),
+            None => String::new(),
+        }
+    }
+
+    /// Registra le funzioni e i record dichiarati a qualsiasi livello di annidamento.
+    /// Una seconda dichiarazione con lo stesso nome è un errore: sovrascriverebbe la prima a runtime.
+    fn collect_functions(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            match stmt {
+                Statement::FunctionDecl { name, params, rest, body, line, .. } => {
+                    let required = params.iter().filter(|(_, default)| default.is_none()).count();
+                    let max_args = if rest.is_some() { usize::MAX } else { params.len() };
+                    let signature = Signature { min_args: required, max_args, line: *line, is_record: false, params: param_names(params) };
+                    self.declare_function(name, signature);
+                    self.collect_functions(body);
+                }
+                Statement::RecordDecl { name, fields, line } => {
+                    let required = fields.iter().filter(|(_, default)| default.is_none()).count();
+                    let signature = Signature { min_args: required, max_args: fields.len(), line: *line, is_record: true, params: param_names(fields) };
+                    self.declare_function(name, signature);
+                }
+                Statement::IfStatement { then_branch, else_branch, .. } => {
+                    self.collect_functions(then_branch);
+                    if let Some(else_stmts) = else_branch {
+                        self.collect_functions(else_stmts);
+                    }
+                }
+                Statement::WhileStatement { body, .. } | Statement::DoWhileStatement { body, .. } | Statement::ForStatement { body, .. } => {
+                    self.collect_functions(body);
+                }
+                Statement::SwitchStatement { cases, default_case, .. } => {
+                    for (_, body) in cases {
+                        self.collect_functions(body);
+                    }
+                    if let Some(body) = default_case {
+                        self.collect_functions(body);
+                    }
+                }
+                Statement::TryCatchStatement { try_block, catch_block, finally_block, .. } => {
+                    self.collect_functions(try_block);
+                    for block in [catch_block, finally_block].into_iter().flatten() {
+                        self.collect_functions(block);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn declare_function(&mut self, name: &str, signature: Signature) {
+        let kind = if signature.is_record { "Record" } else { "Function" };
+        match self.functions.get(name) {
+            Some(previous) => {
+                let msg = format!("{} '{}' is already declared at Line {}", kind, name, previous.line);
+                self.error(signature.line, msg);
+            }
+            None => {
+                self.functions.insert(name.to_string(), signature);
+            }
+        }
+    }
+
+    /// Restituisce la firma di una funzione o di un record dichiarato nel programma.
+    fn find_function(&self, name: &str) -> Option<&Signature> {
+        self.functions.get(name)
+    }
+
+    fn declare_var(&mut self, name: &str, is_mutable: bool, line: usize, warn_unused: bool) {
+        if let Some(idx) = self.find_var(name) {
+            let previous_line = self.scopes[idx].vars[name].line;
+            if idx == self.scopes.len() - 1 {
+                self.warning(line, format!("Variable '{}' is redeclared (previously declared at Line {})", name, previous_line));
+            } else if previous_line < line {
+                // I corpi di funzione sono analizzati a fine scope: una globale dichiarata dopo non è shadowing
+                self.warning(line, format!("Variable '{}' shadows a variable declared at Line {}", name, previous_line));
+            }
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.vars.insert(name.to_string(), Binding { is_mutable, line, used: false, warn_unused });
+        }
+    }
+
+    fn check_function(&mut self, func: &'a Statement) {
+        if let Statement::FunctionDecl { params, rest, body, line, .. } = func {
+            self.fn_scope_starts.push(self.scopes.len());
+            self.enter_scope();
+            // Un valore predefinito vede i parametri che lo precedono
+            for (param, default) in params {
+                if let Some(default) = default {
+                    self.check_expression(default, *line);
+                }
+                self.declare_var(param, true, *line, false);
+            }
+            if let Some(rest) = rest {
+                self.declare_var(rest, true, *line, false);
+            }
+            self.check_block(body);
+            self.exit_scope();
+            self.fn_scope_starts.pop();
+        }
+    }
+
+    fn check_scoped_block(&mut self, stmts: &'a [Statement]) {
+        self.enter_scope();
+        self.check_block(stmts);
+        self.exit_scope();
+    }
+
+    fn check_block(&mut self, stmts: &'a [Statement]) {
+        let mut terminator = None;
+        let mut reported = false;
+        for stmt in stmts {
+            // Le dichiarazioni di funzione sono registrate prima dell'esecuzione del blocco
+            if let Some(keyword) = terminator {
+                if !reported && !matches!(stmt, Statement::FunctionDecl { .. } | Statement::RecordDecl { .. }) {
+                    self.warning(stmt.line(), format!("Unreachable code after '{}'", keyword));
+                    reported = true;
+                }
+            }
+            self.check_statement(stmt);
+            if terminator.is_none() {
+                terminator = match stmt {
+                    Statement::ReturnStatement { .. } => Some("return"),
+                    Statement::ThrowStatement { .. } => Some("throw"),
+                    Statement::Break { .. } => Some("break"),
+                    Statement::Continue { .. } => Some("continue"),
+                    _ => None,
+                };
+            }
+        }
+    }
+
+    fn check_statement(&mut self, stmt: &'a Statement) {
+        match stmt {
+            Statement::VarDecl { is_mutable, name, value, line } => {
+                self.check_expression(value, *line);
+                self.declare_var(name, *is_mutable, *line, true);
+            }
+            Statement::PatternDecl { is_mutable, pattern, value, line } => {
+                self.check_expression(value, *line);
+                self.check_pattern(pattern, *line);
+                for name in pattern.bindings() {
+                    self.declare_var(name, *is_mutable, *line, true);
+                }
+            }
+            Statement::Assignment { target, value, line } => {
+                self.check_expression(value, *line);
+                self.check_assignment_target(target, *line);
+            }
+            Statement::MultiAssignment { targets, values, line } => {
+                for value in values {
+                    self.check_expression(value, *line);
+                }
+                for target in targets {
+                    self.check_assignment_target(target, *line);
+                }
+            }
+            Statement::IfStatement { condition, then_branch, else_branch, line } => {
+                self.check_expression(condition, *line);
+                self.check_scoped_block(then_branch);
+                if let Some(else_stmts) = else_branch {
+                    self.check_scoped_block(else_stmts);
+                }
+            }
+            Statement::WhileStatement { condition, body, line, .. } => {
+                self.check_expression(condition, *line);
+                self.check_scoped_block(body);
+            }
+            Statement::DoWhileStatement { body, condition, line, .. } => {
+                self.check_scoped_block(body);
+                self.check_expression(condition, *line);
+            }
+            Statement::ForStatement { iterator, start, end, body, line, .. } => {
+                self.check_expression(start, *line);
+                if let Some(end) = end {
+                    self.check_expression(end, *line);
+                }
+                self.enter_scope();
+                self.declare_var(iterator, false, *line, false);
+                self.check_block(body);
+                self.exit_scope();
+            }
+            Statement::SwitchStatement { discriminant, cases, default_case, line } => {
+                self.check_expression(discriminant, *line);
+                for (test, body) in cases {
+                    self.check_expression(test, *line);
+                    self.check_scoped_block(body);
+                }
+                if let Some(body) = default_case {
+                    self.check_scoped_block(body);
+                }
+            }
+            Statement::TryCatchStatement { try_block, catch_variable, catch_block, finally_block, line } => {
+                self.check_scoped_block(try_block);
+                if let Some(catch_stmts) = catch_block {
+                    self.enter_scope();
+                    if let Some(var_name) = catch_variable {
+                        self.declare_var(var_name, false, *line, false);
+                    }
+                    self.check_block(catch_stmts);
+                    self.exit_scope();
+                }
+                if let Some(finally_stmts) = finally_block {
+                    self.check_scoped_block(finally_stmts);
+                }
+            }
+            Statement::ThrowStatement { value, line } => {
+                self.check_expression(value, *line);
+            }
+            Statement::ReturnStatement { value, line } => {
+                if self.fn_scope_starts.is_empty() {
+                    self.error(*line, "'return' is only allowed inside functions".to_string());
+                }
+                self.check_expression(value, *line);
+            }
+            Statement::FunctionDecl { .. } => {
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.pending.push(stmt);
+                }
+            }
+            Statement::RecordDecl { fields, line, .. } => {
+                for default in fields.iter().filter_map(|(_, default)| default.as_ref()) {
+                    self.check_expression(default, *line);
+                }
+            }
+            Statement::Yield { value, line } => {
+                self.check_expression(value, *line);
+            }
+            Statement::Break { .. } | Statement::Continue { .. } => {}
+            Statement::Expr { expr, line } => {
+                self.check_expression(expr, *line);
+            }
+        }
+    }
+
+    /// Verifica la destinazione di un'assegnazione: una variabile dichiarata e non costante, o un suo elemento.
+    fn check_assignment_target(&mut self, target: &Expression, line: usize) {
+        let mut current = target;
+        while let Expression::Index { target: inner_target, index } = current {
+            self.check_expression(index, line);
+            current = inner_target;
+        }
+        match current {
+            Expression::Variable(name) => match self.find_var(name) {
+                Some(idx) => {
+                    // `a[i] = v` modifica l'array condiviso da `a`: conta come un uso della variabile
+                    if matches!(target, Expression::Index { .. }) {
+                        self.use_var(name);
+                    }
+                    let binding = &self.scopes[idx].vars[name];
+                    if !binding.is_mutable {
+                        let declared_at = binding.line;
+                        self.error(line, format!("Cannot assign to constant '{}' (declared at Line {})", name, declared_at));
+                    }
+                }
+                None => self.error(line, format!("Assignment to undeclared variable '{}'", name)),
+            },
+            _ => self.error(line, "Invalid assignment target".to_string()),
+        }
+    }
+
+    fn check_expression(&mut self, expr: &Expression, line: usize) {
+        match expr {
+            Expression::LiteralStr(_) | Expression::LiteralInt(_) | Expression::LiteralBigInt(_) | Expression::LiteralFloat(_) | Expression::LiteralBool(_) | Expression::LiteralNull => {}
+            Expression::Array(elements) | Expression::Set(elements) | Expression::Template(elements) => {
+                for element in elements {
+                    self.check_expression(element, line);
+                }
+            }
+            Expression::Map(pairs) => {
+                for (_, value) in pairs {
+                    self.check_expression(value, line);
+                }
+            }
+            Expression::Index { target, index } | Expression::OptionalIndex { target, index } => {
+                self.check_expression(target, line);
+                self.check_expression(index, line);
+            }
+            Expression::Variable(name) => {
+                // Il nome di una funzione è un valore valido (riferimento dinamico a funzione)
+                if !self.use_var(name) && self.find_function(name).is_none() {
+                    let hint = self.name_hint(name);
+                    self.error(line, format!("Undefined variable '{}'{}", name, hint));
+                }
+            }
+            Expression::BinaryOp { left, right, .. } => {
+                self.check_expression(left, line);
+                self.check_expression(right, line);
+            }
+            Expression::UnaryOp { operand, .. } => {
+                self.check_expression(operand, line);
+            }
+            Expression::Ternary { condition, true_expr, false_expr } => {
+                self.check_expression(condition, line);
+                self.check_expression(true_expr, line);
+                self.check_expression(false_expr, line);
+            }
+            Expression::FunctionCall { target, args } => {
+                for arg in args {
+                    self.check_expression(arg, line);
+                }
+                match target.as_ref() {
+                    Expression::Variable(name) => {
+                        self.check_call(name, args.len(), line);
+                        self.check_named_args(name, args, 0, line);
+                    }
+                    other => self.check_expression(other, line),
+                }
+            }
+            Expression::MethodCall { receiver, method, args } => {
+                self.check_expression(receiver, line);
+                for arg in args {
+                    self.check_expression(arg, line);
+                }
+                self.check_method_call(method, args.len() + 1, line);
+                self.check_named_args(method, args, 1, line);
+            }
+            Expression::NamedArg { value, .. } => {
+                self.check_expression(value, line);
+            }
+            Expression::Match { subject, arms, default } => {
+                self.check_expression(subject, line);
+                for arm in arms {
+                    self.check_pattern(&arm.pattern, line);
+                    self.enter_scope();
+                    for name in arm.pattern.bindings() {
+                        self.declare_var(name, false, line, false);
+                    }
+                    if let Some(guard) = &arm.guard {
+                        self.check_expression(guard, line);
+                    }
+                    self.check_expression(&arm.value, line);
+                    self.exit_scope();
+                }
+                match default {
+                    Some(default) => self.check_expression(default, line),
+                    None if !is_exhaustive(arms) => {
+                        self.warning(line, "'match' is not exhaustive: add a 'default' arm or a catch-all 'case'".to_string());
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    /// Verifica le espressioni contenute in un pattern e i record che destruttura.
+    fn check_pattern(&mut self, pattern: &Pattern, line: usize) {
+        match pattern {
+            Pattern::Wildcard | Pattern::Binding(_) => {}
+            Pattern::Literal(expr) => self.check_expression(expr, line),
+            Pattern::Range { start, end } => {
+                self.check_expression(start, line);
+                self.check_expression(end, line);
+            }
+            Pattern::Alternatives(options) => {
+                for option in options {
+                    self.check_pattern(option, line);
+                }
+            }
+            Pattern::Array { items, rest } => {
+                for item in items.iter().chain(rest.as_deref()) {
+                    self.check_pattern(item, line);
+                }
+            }
+            Pattern::Map(entries) => {
+                for (_, item) in entries {
+                    self.check_pattern(item, line);
+                }
+            }
+            Pattern::Record { name, fields } => {
+                match self.find_function(name) {
+                    Some(signature) if signature.is_record => {
+                        if fields.len() != signature.max_args {
+                            let expected = signature.max_args;
+                            self.error(line, format!(
+                                "Record pattern '{}' expects {} field(s) but {} were provided",
+                                name, expected, fields.len()
+                            ));
+                        }
+                    }
+                    _ => {
+                        let hint = self.name_hint(name);
+                        self.error(line, format!("Undefined record '{}' in pattern{}", name, hint));
+                    }
+                }
+                for field in fields {
+                    self.check_pattern(field, line);
+                }
+            }
+            Pattern::Type { inner, .. } => {
+                if let Some(inner) = inner {
+                    self.check_pattern(inner, line);
+                }
+            }
+        }
+    }
+
+    /// Verifica l'arità di un metodo che corrisponde a un built-in o a una funzione (contando il destinatario).
+    /// Un nome sconosciuto non è un errore: può essere una chiave della mappa su cui è chiamato.
+    fn check_method_call(&mut self, method: &str, args_count: usize, line: usize) {
+        if builtins::is_builtin(method) || self.find_function(method).is_some() {
+            self.check_call(method, args_count, line);
+        }
+    }
+
+    /// Verifica gli argomenti con nome di una chiamata: nomi sconosciuti o già assegnati per posizione
+    /// e parametri obbligatori rimasti senza valore. `offset` conta gli argomenti impliciti (il destinatario di un metodo).
+    fn check_named_args(&mut self, name: &str, args: &[Expression], offset: usize, line: usize) {
+        let named: Vec<&str> = args.iter().filter_map(|arg| match arg {
+            Expression::NamedArg { name, .. } => Some(name.as_str()),
+            _ => None,
+        }).collect();
+        if named.is_empty() || self.find_var(name).is_some() {
+            return;
+        }
+        if builtins::is_builtin(name) {
+            self.error(line, format!("Built-in function '{}' does not accept named arguments", name));
+            return;
+        }
+        let Some(signature) = self.find_function(name) else { return };
+        let kind = if signature.is_record { "Record" } else { "Function" };
+        let params = signature.params.clone();
+        // Con troppi pochi argomenti l'errore di arità è già stato segnalato da `check_call`
+        let arity_ok = offset + args.len() >= signature.min_args;
+        let positional = offset + args.len() - named.len();
+        for arg in &named {
+            match params.iter().position(|(param, _)| param == arg) {
+                Some(idx) if idx < positional => {
+                    self.error(line, format!("{} '{}' receives more than one value for parameter '{}'", kind, name, arg));
+                }
+                Some(_) => {}
+                None => self.error(line, format!("{} '{}' has no parameter named '{}'", kind, name, arg)),
+            }
+        }
+        let missing = params.iter().skip(positional).filter(|(param, has_default)| !has_default && !named.contains(&param.as_str()));
+        for (param, _) in missing.filter(|_| arity_ok) {
+            self.error(line, format!("{} '{}' is missing an argument for parameter '{}'", kind, name, param));
+        }
+    }
+
+    /// Verifica l'esistenza e l'arità di una funzione chiamata per nome.
+    /// Una variabile con lo stesso nome rende la chiamata dinamica (risolta a runtime).
+    fn check_call(&mut self, name: &str, args_count: usize, line: usize) {
+        let is_dynamic = self.use_var(name);
+        if let Some(valid) = builtins::is_builtin_arity_valid(name, args_count) {
+            if !valid && !is_dynamic {
+                self.error(line, format!("Wrong number of arguments for built-in function '{}' (provided {})", name, args_count));
+            }
+            return;
+        }
+        match self.find_function(name) {
+            Some(signature) => {
+                if !(signature.min_args..=signature.max_args).contains(&args_count) && !is_dynamic {
+                    let kind = if signature.is_record { "Record" } else { "Function" };
+                    let expected = if signature.min_args == signature.max_args {
+                        signature.min_args.to_string()
+                    } else if signature.max_args == usize::MAX {
+                        format!("at least {}", signature.min_args)
+                    } else {
+                        format!("{} to {}", signature.min_args, signature.max_args)
+                    };
+                    self.error(line, format!(
+                        "{} '{}' expects {} argument(s) but {} were provided",
+                        kind, name, expected, args_count
+                    ));
+                }
+            }
+            None => {
+                if !is_dynamic {
+                    let hint = self.name_hint(name);
+                    self.error(line, format!("Undefined function '{}'{}", name, hint));
+                }
+            }
+        }
+    }
+}
+
+/// Nomi dei parametri di una dichiarazione e se hanno un valore predefinito.
+fn param_names(params: &[(String, Option<Expression>)]) -> Vec<(String, bool)> {
+    params.iter().map(|(name, default)| (name.clone(), default.is_some())).collect()
+}
+
+/// Un `match` senza `default` è esaustivo se un ramo senza guardia accetta qualsiasi valore
+/// o se i rami senza guardia coprono sia `true` sia `false`.
+fn is_exhaustive(arms: &[MatchArm]) -> bool {
+    let unguarded: Vec<&Pattern> = arms.iter().filter(|arm| arm.guard.is_none()).map(|arm| &arm.pattern).collect();
+    let covers = |wanted: bool| {
+        unguarded.iter().any(|pattern| {
+            let options = match pattern {
+                Pattern::Alternatives(options) => options.as_slice(),
+                single => std::slice::from_ref(*single),
+            };
+            options.iter().any(|p| matches!(p, Pattern::Literal(Expression::LiteralBool(b)) if *b == wanted))
+        })
+    };
+    unguarded.iter().any(|pattern| pattern.is_irrefutable()) || (covers(true) && covers(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Engine;
+
+    /// Esegue import, lexing, parsing ed analisi semantica di un sorgente.
+    fn check_source(source: &str) -> (Vec<String>, Vec<String>) {
+        let mut engine = Engine::new();
+        let (stripped, import_mgr, _) = crate::engine::check::validate_imports(source, &mut engine.translation_engine);
+        let filtered = crate::engine::filter::FilteredEngine::new(&engine.translation_engine, &import_mgr);
+        let mut lexer = crate::engine::lexer::Lexer::new(&stripped);
+        let tokens = lexer.tokenize(&engine.translation_engine, &filtered);
+        let mut parser = crate::engine::parser::Parser::new(tokens);
+        let program = parser.parse(&engine.translation_engine, &import_mgr).unwrap();
+        let mut checker = Checker::new(Suggestions::new(&engine.translation_engine, &import_mgr));
+        checker.check(&program);
+        (checker.errors, checker.warnings)
+    }
+
+    #[test]
+    fn test_semantic_errors() {
+        let source = "importa italiano da translate\n\
+            crea a = b + 1\n\
+            fissa c = 1\n\
+            c = 2\n\
+            funzione somma(x, y) {\n\
+            ritorna x + y\n\
+            }\n\
+            crea s = somma(1)\n\
+            crea t = inesistente(a, s, t)\n\
+            ritorna 0\n";
+        let (errors, _) = check_source(source);
+
+        assert_eq!(errors.len(), 6);
+        assert!(errors[0].contains("Line 2") && errors[0].contains("Undefined variable 'b'"));
+        assert!(errors[1].contains("Line 4") && errors[1].contains("Cannot assign to constant 'c'"));
+        assert!(errors[2].contains("Line 8") && errors[2].contains("expects 2 argument(s) but 1 were provided"));
+        assert!(errors[3].contains("Line 9") && errors[3].contains("Undefined variable 't'"));
+        assert!(errors[4].contains("Line 9") && errors[4].contains("Undefined function 'inesistente'"));
+        assert!(errors[5].contains("Line 10") && errors[5].contains("'return' is only allowed inside functions"));
+    }
+
+    #[test]
+    fn test_function_bodies_see_later_declarations() {
+        // Ricorsione mutua, globali dichiarate dopo la funzione e chiamate dinamiche tramite stringa
+        let source = "importa italiano da translate\n\
+            funzione pari(n) {\n\
+            se (n == 0) { ritorna vero }\n\
+            ritorna dispari(n - 1) && limite > 0\n\
+            }\n\
+            funzione dispari(n) {\n\
+            se (n == 0) { ritorna falso }\n\
+            ritorna pari(n - 1)\n\
+            }\n\
+            crea limite = 10\n\
+            crea nome = \"pari\"\n\
+            crea r = nome(4, 5)\n\
+            crea p = pari(r)\n";
+        let (errors, _) = check_source(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_duplicate_function_declarations() {
+        let source = "importa italiano da translate\n\
+            funzione f() {\n\
+            ritorna 1\n\
+            }\n\
+            se (vero) {\n\
+            funzione f() {\n\
+            ritorna 2\n\
+            }\n\
+            }\n\
+            crea x = g()\n\
+            funzione g() {\n\
+            ritorna f()\n\
+            }\n";
+        let (errors, _) = check_source(source);
+
+        assert_eq!(errors, vec!["Semantic Error (Line 6): Function 'f' is already declared at Line 2".to_string()]);
+    }
+
+    #[test]
+    fn test_record_constructor_arity() {
+        let source = "importa italiano da translate\n\
+            struttura Punto { x, y = 0 }\n\
+            crea a = Punto(1)\n\
+            crea b = Punto(1, 2)\n\
+            crea c = Punto()\n\
+            funzione Punto() {\n\
+            ritorna 0\n\
+            }\n\
+            crea d = a == b || c\n";
+        let (errors, _) = check_source(source);
+
+        assert_eq!(errors, vec![
+            "Semantic Error (Line 5): Record 'Punto' expects 1 to 2 argument(s) but 0 were provided".to_string(),
+            "Semantic Error (Line 6): Function 'Punto' is already declared at Line 2".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_method_call_arity() {
+        let source = "importa italiano da translate\n\
+            funzione doppio(n) {\n\
+            ritorna n * 2\n\
+            }\n\
+            crea a = [1].lunghezza()\n\
+            crea b = a.doppio()\n\
+            crea c = b.doppio(1)\n\
+            crea d = [c].lunghezza(1)\n\
+            crea e = {}.ignoto(d)\n";
+        let (errors, _) = check_source(source);
+
+        assert_eq!(errors, vec![
+            "Semantic Error (Line 7): Function 'doppio' expects 1 argument(s) but 2 were provided".to_string(),
+            "Semantic Error (Line 8): Wrong number of arguments for built-in function 'len' (provided 2)".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_default_rest_and_named_parameters() {
+        let source = "importa italiano da translate\n\
+            funzione f(a, b = a, ...altri) {\n\
+            ritorna a + b + lunghezza(altri)\n\
+            }\n\
+            struttura Punto { x, y = 0 }\n\
+            crea v = f(1) + f(1, 2, 3, 4) + f(b = 2, a = 1)\n\
+            crea p = Punto(y = v, x = 1)\n\
+            crea q = f()\n\
+            crea r = f(1, a = 2)\n\
+            crea s = Punto(z = 1, x = 1)\n\
+            crea t = Punto(y = 1)\n\
+            crea u = lunghezza(x = [p, q, r, s, t])\n";
+        let (errors, _) = check_source(source);
+
+        assert_eq!(errors, vec![
+            "Semantic Error (Line 8): Function 'f' expects at least 1 argument(s) but 0 were provided".to_string(),
+            "Semantic Error (Line 9): Function 'f' receives more than one value for parameter 'a'".to_string(),
+            "Semantic Error (Line 10): Record 'Punto' has no parameter named 'z'".to_string(),
+            "Semantic Error (Line 11): Record 'Punto' is missing an argument for parameter 'x'".to_string(),
+            "Semantic Error (Line 12): Built-in function 'len' does not accept named arguments".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_match_exhaustiveness_and_patterns() {
+        let source = "importa italiano da translate\n\
+            struttura Punto { x, y = 0 }\n\
+            crea a = confronta (1) { caso 0: 1 caso n se n > 0: n }\n\
+            crea b = confronta (a) { caso vero: 1 caso falso: 2 }\n\
+            crea c = confronta (b) { caso 0: 1 caso _: 2 }\n\
+            crea d = confronta (c) { caso Punto(x, y): x + y caso [p, ...resto]: p + resto predefinito: 0 }\n\
+            crea e = confronta (d) { caso Punto(x): x caso Pnto(x, y): x + y caso altro: altro }\n\
+            crea f = confronta (e) { caso [q]: q + ignota predefinito: 0 }\n";
+        let (errors, warnings) = check_source(source);
+
+        assert_eq!(errors, vec![
+            "Semantic Error (Line 7): Record pattern 'Punto' expects 2 field(s) but 1 were provided".to_string(),
+            "Semantic Error (Line 7): Undefined record 'Pnto' in pattern; did you mean 'Punto'?".to_string(),
+            "Semantic Error (Line 8): Undefined variable 'ignota'".to_string(),
+        ]);
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(warnings[0].contains("Line 3") && warnings[0].contains("'match' is not exhaustive"));
+        assert!(warnings[1].contains("Line 8") && warnings[1].contains("'f' is declared but never used"));
+    }
+
+    #[test]
+    fn test_destructuring_and_multi_assignment() {
+        let source = "importa italiano da translate\n\
+            fissa [a, b] = [1, 2]\n\
+            crea {nome, eta} = {\"nome\": a}\n\
+            crea c = b\n\
+            a, c = c, nome\n\
+            c, ignota = 1, 2\n";
+        let (errors, warnings) = check_source(source);
+
+        assert_eq!(errors, vec![
+            "Semantic Error (Line 5): Cannot assign to constant 'a' (declared at Line 2)".to_string(),
+            "Semantic Error (Line 6): Assignment to undeclared variable 'ignota'".to_string(),
+        ]);
+        assert_eq!(warnings, vec![
+            "Warning (Line 3): Variable 'eta' is declared but never used".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_semantic_warnings() {
+        let source = "importa italiano da translate\n\
+            importa * da nio\n\
+            crea x = 1\n\
+            funzione f() {\n\
+            crea x = 2\n\
+            ritorna x\n\
+            stampa(\"mai\")\n\
+            }\n\
+            crea inutile = f()\n\
+            crea _ignorata = 3\n\
+            crea alias = [1]\n\
+            alias[0] = 2\n\
+            stampa(x)\n";
+        let (errors, warnings) = check_source(source);
+
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("Line 5") && warnings[0].contains("shadows a variable declared at Line 3"));
+        assert!(warnings[1].contains("Line 7") && warnings[1].contains("Unreachable code after 'return'"));
+        assert!(warnings[2].contains("Line 9") && warnings[2].contains("'inutile' is declared but never used"));
+    }
+
+    #[test]
+    fn test_shadowing_only_reports_earlier_declarations() {
+        let source = "importa italiano da translate\n\
+            importa * da nio\n\
+            funzione f() {\n\
+            crea x = 2\n\
+            ritorna x\n\
+            }\n\
+            crea x = f()\n\
+            stampa(x)\n";
+        let (errors, warnings) = check_source(source);
+
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+}
@@ -10,15 +10,21 @@ pub mod interpreter;
 pub mod value;
 #[path = "translate/translate.rs"]
 pub mod translate;
+#[path = "translate/suggest.rs"]
+pub mod suggest;
 #[path = "import/import.rs"]
 pub mod import;
 #[path = "import/check.rs"]
 pub mod check;
 #[path = "filter/filter.rs"]
 pub mod filter;
+#[path = "checker/checker.rs"]
+pub mod checker;
 
 use self::lexer::Lexer;
 use self::parser::Parser;
+use self::checker::Checker;
+use self::suggest::Suggestions;
 use self::interpreter::Interpreter;
 use self::translate::TranslationEngine;
 use self::import::ImportManager;
@@ -28,6 +34,10 @@ pub struct Engine {
     pub import_manager: ImportManager,
     pub interpreter: Interpreter,
     pub quiet: bool,
+    // Stampa i valori in formato neutro (true/false/null, punto decimale) invece che nella lingua del programma
+    pub machine_output: bool,
+    // Ordina le stringhe ignorando maiuscole e accenti invece che per code point
+    pub collate: bool,
 }
 
 impl Engine {
@@ -41,23 +51,51 @@ impl Engine {
             import_manager,
             interpreter,
             quiet: false,
+            machine_output: false,
+            collate: false,
+        }
+    }
+
+    /// Registra i language pack dalla cartella dell'utente (~/.nodestract/languages, se esiste)
+    /// e dai percorsi indicati con `--lang-pack` (file JSON o cartelle).
+    /// Un pack che non è possibile registrare viene segnalato con un avviso e ignorato:
+    /// il contenuto dei pack viene validato solo quando un programma li importa.
+    pub fn load_language_packs(&mut self, paths: &[String]) {
+        let mut errors = Vec::new();
+        if let Some(dir) = TranslationEngine::default_pack_dir().filter(|dir| dir.is_dir()) {
+            errors.append(&mut self.translation_engine.load_pack_dir(&dir));
+        }
+        for path in paths.iter().map(std::path::Path::new) {
+            if path.is_dir() {
+                errors.append(&mut self.translation_engine.load_pack_dir(path));
+            } else if let Err(mut pack_errors) = self.translation_engine.load_pack(path) {
+                errors.append(&mut pack_errors);
+            }
+        }
+        if !self.quiet {
+            for error in &errors {
+                crate::welcome::show_warning(error);
+            }
         }
     }
 
     /// Avvia la pipeline completa di NodeStract per un sorgente fornito.
     /// Restituisce `true` se l'esecuzione è terminata senza errori, `false` altrimenti.
     pub fn run(&mut self, source: &str) -> bool {
-        // 1. Estrae e valida gli import (riga per riga)
-        let (stripped_source, active_import_manager) = match check::validate_imports(source, &self.translation_engine) {
-            Ok(res) => res,
-            Err(err_msg) => {
-                if !self.quiet {
-                    crate::welcome::show_error(&err_msg);
-                }
-                return false;
-            }
-        };
+        // 1. Estrae e valida gli import (riga per riga), raccogliendo tutti gli errori
+        let (stripped_source, active_import_manager, mut errors) = check::validate_imports(source, &mut self.translation_engine);
         self.import_manager = active_import_manager;
+        if !self.quiet {
+            for warning in self.translation_engine.take_pack_warnings() {
+                crate::welcome::show_warning(&warning);
+            }
+        }
+
+        // Senza una lingua attiva il sorgente non può essere tokenizzato in modo sensato
+        if !self.import_manager.is_parent_active("translate") {
+            self.report_errors(&errors);
+            return false;
+        }
 
         // 2. Costruisce il vocabolario di keyword attive (FilteredEngine)
         let filtered_engine = filter::FilteredEngine::new(&self.translation_engine, &self.import_manager);
@@ -67,30 +105,62 @@ impl Engine {
         let final_tokens = lexer.tokenize(&self.translation_engine, &filtered_engine);
 
         // 4. Esegue il parsing e la validazione sintattica dei delimitatori
-        let mut parser = Parser::new(final_tokens.clone());
-        match parser.parse(&self.translation_engine, &self.import_manager) {
-            Ok(program) => {
-                self.interpreter = Interpreter::new();
-                self.interpreter.run(program);
-                if let Some(ref exc) = self.interpreter.exception {
-                    if !self.quiet {
-                        let exc_str = match exc {
-                            crate::engine::value::Value::String(s) => s.clone(),
-                            other => other.to_string(),
-                        };
-                        crate::welcome::show_error(&format!("Uncaught Exception: {}", exc_str));
-                    }
-                    return false;
-                }
-                true
+        let mut parser = Parser::new(final_tokens);
+        let program = match parser.parse(&self.translation_engine, &self.import_manager) {
+            Ok(program) => program,
+            Err(mut parse_errors) => {
+                errors.append(&mut parse_errors);
+                self.report_errors(&errors);
+                return false;
             }
-            Err(err_msg) => {
-                if !self.quiet {
-                    crate::welcome::show_error(&err_msg);
-                }
-                false
+        };
+
+        // 5. Analisi semantica statica: errori bloccanti e warning non bloccanti
+        let mut checker = Checker::new(Suggestions::new(&self.translation_engine, &self.import_manager));
+        checker.check(&program);
+        if !self.quiet {
+            for warning in &checker.warnings {
+                crate::welcome::show_warning(warning);
             }
         }
+        errors.append(&mut checker.errors);
+        if !errors.is_empty() {
+            self.report_errors(&errors);
+            return false;
+        }
+
+        // 6. Esegue il programma
+        self.interpreter = Interpreter::new();
+        if let Some(language) = self.import_manager.primary_language().filter(|_| !self.machine_output) {
+            self.interpreter.locale = self.translation_engine.locale(language);
+        }
+        self.interpreter.locale.collate = self.collate;
+        self.interpreter.network_send = self.import_manager.is_member_active("send", "nnet");
+        self.interpreter.run(program);
+        if let Some(ref exc) = self.interpreter.exception {
+            if !self.quiet {
+                let exc_str = match exc {
+                    crate::engine::value::Value::String(s) => s.clone(),
+                    other => other.display(&self.interpreter.locale),
+                };
+                crate::welcome::show_error(&format!("Uncaught Exception: {}", exc_str));
+            }
+            return false;
+        }
+        true
+    }
+
+    /// Mostra tutti gli errori di compilazione raccolti, seguiti da un riepilogo.
+    fn report_errors(&self, errors: &[String]) {
+        if self.quiet {
+            return;
+        }
+        for err in errors {
+            crate::welcome::show_error(err);
+        }
+        if errors.len() > 1 {
+            crate::welcome::show_error(&format!("Compilation failed with {} errors.", errors.len()));
+        }
     }
 
     /// Legge un file da disco e lo esegue nella pipeline.
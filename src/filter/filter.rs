@@ -1,7 +1,14 @@
 use std::collections::HashMap;
-use crate::engine::translate::TranslationEngine;
+use crate::engine::translate::{is_builtin_module, TranslationEngine};
 use crate::engine::import::ImportManager;
 
+/// Keyword contestuali: non sono mai parole riservate (restano utilizzabili come nomi di variabile)
+/// e il parser le riconosce solo nella posizione in cui hanno significato: i tipi nei pattern di `match`,
+/// i cicli all'inizio di uno statement seguiti da `{` e `until` dopo il corpo di `repeat`.
+pub const CONTEXTUAL_KEYWORDS: [&str; 10] = [
+    "boolean", "integer", "float", "string", "array", "map", "loop", "do", "repeat", "until",
+];
+
 pub struct FilteredEngine {
     // Mappa le parole normalizzate in minuscolo alle loro forme canoniche inglesi
     active_keywords: HashMap<String, String>,
@@ -15,40 +22,21 @@ impl FilteredEngine {
         for (normalized_word, candidates) in &translation.keyword_map {
             for (canonical, module, language) in candidates {
                 // Disabilita le keyword di bootstrap ed i nomi delle lingue
-                if canonical == "import"
-                    || canonical == "from"
-                    || canonical == "english"
-                    || canonical == "italian"
-                    || canonical == "spanish"
-                    || canonical == "french"
-                    || canonical == "german"
-                    || canonical == "portuguese"
-                    || canonical == "romanian"
-                {
+                if canonical == "import" || canonical == "from" || translation.is_language(canonical) {
+                    continue;
+                }
+                if CONTEXTUAL_KEYWORDS.contains(&canonical.as_str()) {
                     continue;
                 }
 
-                // Conserva la keyword se la lingua di appartenenza è attiva
-                if language == "english"
-                    || language == "italian"
-                    || language == "spanish"
-                    || language == "french"
-                    || language == "german"
-                    || language == "portuguese"
-                    || language == "romanian"
-                {
+                // Conserva la keyword se la lingua di appartenenza è attiva e, per i built-in,
+                // se il rispettivo modulo è importato: altrimenti la parola resta un nome libero
+                let module_active = !is_builtin_module(module) || import_manager.is_member_imported(canonical);
+                if translation.is_language(language) && module_active {
+                    // I candidati sono nell'ordine di caricamento delle lingue: in caso di collisione
+                    // (già segnalata come errore di import) vince sempre la prima, mai l'ordine dell'hash
                     if import_manager.is_member_active(language, "translate") {
-                        active_keywords.insert(normalized_word.clone(), canonical.clone());
-                    }
-                }
-                // Conserva il built-in se il rispettivo modulo è attivo
-                else if module == "nio"
-                    || module == "nmath"
-                    || module == "nfs"
-                    || module == "nnet"
-                {
-                    if import_manager.is_member_active(canonical, module) {
-                        active_keywords.insert(normalized_word.clone(), canonical.clone());
+                        active_keywords.entry(normalized_word.clone()).or_insert_with(|| canonical.clone());
                     }
                 }
             }
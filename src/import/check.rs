@@ -1,16 +1,26 @@
+use std::collections::HashMap;
 use crate::engine::import::ImportManager;
 use crate::engine::translate::TranslationEngine;
 
 /// Valida gli import all'inizio del file, li registra e restituisce il sorgente ripulito dagli import.
+/// Non si ferma al primo errore: restituisce anche la lista di tutti gli errori di import trovati.
+/// I language pack importati vengono attivati (e validati) nel momento in cui compaiono negli import.
 pub fn validate_imports(
     source: &str,
-    translation_engine: &TranslationEngine,
-) -> Result<(String, ImportManager), String> {
+    translation_engine: &mut TranslationEngine,
+) -> (String, ImportManager, Vec<String>) {
+    let mut errors = Vec::new();
     let mut import_manager = ImportManager::new();
+    // Le lingue caricate dai language pack diventano importabili da "translate"
+    for language in translation_engine.languages() {
+        import_manager.register_member(language, "translate");
+    }
     let mut stripped_lines = Vec::new();
     let mut imports_ended = false;
     let mut inside_multiline_comment = false;
     let mut has_imported_any_language = false;
+    // Riga in cui è stata importata ciascuna lingua ("*" per l'import di tutte le lingue)
+    let mut language_lines: HashMap<String, usize> = HashMap::new();
 
     for (line_num, line) in source.lines().enumerate() {
         let mut clean_line = line.to_string();
@@ -67,22 +77,28 @@ pub fn validate_imports(
         let temp_manager = ImportManager::new();
         let is_import_stmt = translation_engine
             .lookup(words[0], &temp_manager)
-            .map_or(false, |kw| kw == "import");
+            .is_some_and(|kw| kw == "import");
 
         if is_import_stmt {
+            // Sostituisce la riga con una vuota per mantenere corretti i numeri di riga negli errori
+            // (anche quando l'import è errato, per non generare errori a cascata nel parser)
+            stripped_lines.push("".to_string());
+
             if imports_ended {
-                return Err(format!(
+                errors.push(format!(
                     "Syntax Error (Line {}): Import statement found after non-import code",
                     line_num + 1
                 ));
+                continue;
             }
 
             let len = words.len();
             if len < 4 {
-                return Err(format!(
+                errors.push(format!(
                     "Syntax Error (Line {}): Invalid import syntax. Expected: import <member(s)> from <parent>",
                     line_num + 1
                 ));
+                continue;
             }
 
             let parent = words[len - 1];
@@ -91,14 +107,15 @@ pub fn validate_imports(
             // Controlla il keyword "from"
             let is_from = translation_engine
                 .lookup(from_keyword, &temp_manager)
-                .map_or(false, |kw| kw == "from");
+                .is_some_and(|kw| kw == "from");
 
             if !is_from {
-                return Err(format!(
+                errors.push(format!(
                     "Syntax Error (Line {}): Expected 'from' keyword, found '{}'",
                     line_num + 1,
                     from_keyword
                 ));
+                continue;
             }
 
             let members = &words[1..len - 2];
@@ -110,21 +127,31 @@ pub fn validate_imports(
 
                 // Registra l'import
                 if !import_manager.import_member(canonical_member, parent) {
-                    return Err(format!(
+                    errors.push(format!(
                         "Import Error (Line {}): Cannot import '{}' from '{}'",
                         line_num + 1,
                         member,
                         parent
                     ));
+                    continue;
                 }
 
                 if parent == "translate" {
                     has_imported_any_language = true;
+                    let canonical_member = canonical_member.to_string();
+                    let activated: Vec<String> = if canonical_member == "*" {
+                        translation_engine.languages().to_vec()
+                    } else {
+                        vec![canonical_member.clone()]
+                    };
+                    for language in activated {
+                        if let Err(mut pack_errors) = translation_engine.activate_pack(&language) {
+                            errors.append(&mut pack_errors);
+                        }
+                    }
+                    language_lines.entry(canonical_member).or_insert(line_num + 1);
                 }
             }
-
-            // Sostituisce la riga con una vuota per mantenere corretti i numeri di riga negli errori
-            stripped_lines.push("".to_string());
         } else {
             // Trovata la prima riga di codice reale, chiude la fase di import
             imports_ended = true;
@@ -132,14 +159,35 @@ pub fn validate_imports(
         }
     }
 
+    // Una parola con significati diversi in due lingue importate renderebbe ambiguo il sorgente:
+    // l'errore è segnalato sulla riga dell'import che introduce la seconda lingua
+    let import_line = |language: &str| {
+        language_lines.get(language).or_else(|| language_lines.get("*")).copied().unwrap_or(0)
+    };
+    for conflict in translation_engine.find_conflicts() {
+        if import_manager.is_member_active(&conflict.first_language, "translate")
+            && import_manager.is_member_active(&conflict.second_language, "translate")
+        {
+            errors.push(format!(
+                "Import Error (Line {}): Keyword conflict: '{}' means '{}' in {} but '{}' in {}",
+                import_line(&conflict.first_language).max(import_line(&conflict.second_language)),
+                conflict.word,
+                conflict.first_keyword,
+                conflict.first_language,
+                conflict.second_keyword,
+                conflict.second_language
+            ));
+        }
+    }
+
     if !has_imported_any_language {
-        return Err(
+        errors.push(
             "Import Error: No language imported. You must import at least one language (e.g., 'english', 'italian') from 'translate' at the beginning of the file."
                 .to_string(),
         );
     }
 
-    Ok((stripped_lines.join("\n"), import_manager))
+    (stripped_lines.join("\n"), import_manager, errors)
 }
 
 #[cfg(test)]
@@ -149,8 +197,9 @@ mod tests {
     #[test]
     fn test_line_count_preservation() {
         let source = "importa italiano da translate\n// Un commento\n\nscrivi(\"ciao\")";
-        let translation_engine = TranslationEngine::new();
-        let (stripped, _) = validate_imports(source, &translation_engine).unwrap();
+        let mut translation_engine = TranslationEngine::new();
+        let (stripped, _, errors) = validate_imports(source, &mut translation_engine);
+        assert!(errors.is_empty());
         
         let original_lines: Vec<&str> = source.lines().collect();
         let stripped_lines: Vec<&str> = stripped.lines().collect();
@@ -166,12 +215,61 @@ mod tests {
     #[test]
     fn test_inline_comments_in_imports() {
         let source = "importa italiano da translate // commento in linea\nscrivi(\"ciao\")";
-        let translation_engine = TranslationEngine::new();
-        let (stripped, import_manager) = validate_imports(source, &translation_engine).unwrap();
+        let mut translation_engine = TranslationEngine::new();
+        let (stripped, import_manager, errors) = validate_imports(source, &mut translation_engine);
+        assert!(errors.is_empty());
         
         assert!(import_manager.is_member_active("italian", "translate"));
         let stripped_lines: Vec<&str> = stripped.lines().collect();
         assert_eq!(stripped_lines[0], "");
         assert_eq!(stripped_lines[1], "scrivi(\"ciao\")");
     }
+
+    #[test]
+    fn test_member_shared_between_modules() {
+        // `invia` (send) appartiene sia a nnet che a nconc
+        let source = "importa italiano da translate\nimporta invia da nconc\ninvia(c, 1)";
+        let mut translation_engine = TranslationEngine::new();
+        let (_, import_manager, errors) = validate_imports(source, &mut translation_engine);
+        assert!(errors.is_empty());
+        assert!(import_manager.is_member_active("send", "nconc"));
+    }
+
+    #[test]
+    fn test_all_import_errors_reported() {
+        let source = "importa italiano da translate\nimporta pippo da nio\nimporta sin\ncrea x = 1\nimporta * da nmath";
+        let mut translation_engine = TranslationEngine::new();
+        let (stripped, import_manager, errors) = validate_imports(source, &mut translation_engine);
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].contains("Line 2") && errors[0].contains("Cannot import 'pippo'"));
+        assert!(errors[1].contains("Line 3") && errors[1].contains("Invalid import syntax"));
+        assert!(errors[2].contains("Line 5") && errors[2].contains("after non-import code"));
+
+        // Gli import validi restano registrati e tutte le righe di import vengono svuotate
+        assert!(import_manager.is_member_active("italian", "translate"));
+        let stripped_lines: Vec<&str> = stripped.split('\n').collect();
+        assert_eq!(stripped_lines, vec!["", "", "", "crea x = 1", ""]);
+    }
+
+    #[test]
+    fn test_keyword_conflicts_between_imported_languages() {
+        let mut translation_engine = TranslationEngine::new();
+        // Simula una traduzione spagnola di "in" che collide con il "se" italiano
+        translation_engine
+            .keyword_map
+            .entry("se".to_string())
+            .or_default()
+            .push(("in".to_string(), "".to_string(), "spanish".to_string()));
+
+        let (_, _, errors) = validate_imports("importa italiano da translate\ncrea x = 1", &mut translation_engine);
+        assert!(errors.is_empty());
+
+        let source = "importa italiano da translate\nimporta spagnolo da translate\ncrea x = 1";
+        let (_, _, errors) = validate_imports(source, &mut translation_engine);
+        assert_eq!(
+            errors,
+            vec!["Import Error (Line 2): Keyword conflict: 'se' means 'if' in italian but 'in' in spanish".to_string()]
+        );
+    }
 }
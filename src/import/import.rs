@@ -1,12 +1,14 @@
 use std::collections::{HashMap, HashSet};
 
 pub struct ImportManager {
-    // Associa un modulo genitore ai suoi sottomoduli/funzioni
-    allowed_imports: HashMap<String, HashSet<String>>,
+    // Associa un modulo genitore ai suoi sottomoduli/funzioni, nell'ordine di import.json
+    allowed_imports: HashMap<String, Vec<String>>,
     // Moduli genitori attivi
     active_parents: HashSet<String>,
-    // Singoli membri importati singolarmente
-    active_members: HashSet<String>,
+    // Membri importati, ciascuno insieme al modulo genitore da cui proviene
+    active_members: HashSet<(String, String)>,
+    // Lingue importate da "translate", nell'ordine in cui compaiono negli import
+    imported_languages: Vec<String>,
 }
 
 impl ImportManager {
@@ -14,19 +16,22 @@ impl ImportManager {
     pub fn new() -> Self {
         let json_content = include_str!("import.json");
         let allowed: HashMap<String, Vec<String>> = serde_json::from_str(json_content).unwrap_or_default();
-        let allowed_imports = allowed
-            .into_iter()
-            .map(|(k, v)| (k, v.into_iter().collect()))
-            .collect();
-
         Self {
-            allowed_imports,
+            allowed_imports: allowed,
             active_parents: HashSet::new(),
             active_members: HashSet::new(),
+            imported_languages: Vec::new(),
         }
     }
 
 
+    /// Registra un nuovo membro importabile da un modulo genitore (es. una lingua caricata da un language pack).
+    pub fn register_member(&mut self, member: &str, parent: &str) {
+        let members = self.allowed_imports.entry(parent.to_string()).or_default();
+        if !members.iter().any(|m| m == member) {
+            members.push(member.to_string());
+        }
+    }
 
     /// Importa un membro specifico da un modulo genitore.
     /// Il carattere jolly "*" importa tutti i membri.
@@ -35,7 +40,10 @@ impl ImportManager {
             if let Some(members) = self.allowed_imports.get(parent) {
                 self.active_parents.insert(parent.to_string());
                 for m in members {
-                    self.active_members.insert(m.clone());
+                    self.active_members.insert((m.clone(), parent.to_string()));
+                    if parent == "translate" && !self.imported_languages.contains(m) {
+                        self.imported_languages.push(m.clone());
+                    }
                 }
                 return true;
             }
@@ -43,9 +51,12 @@ impl ImportManager {
         }
 
         if let Some(members) = self.allowed_imports.get(parent) {
-            if members.contains(member) {
+            if members.iter().any(|m| m == member) {
                 self.active_parents.insert(parent.to_string());
-                self.active_members.insert(member.to_string());
+                self.active_members.insert((member.to_string(), parent.to_string()));
+                if parent == "translate" && !self.imported_languages.iter().any(|l| l == member) {
+                    self.imported_languages.push(member.to_string());
+                }
                 true
             } else {
                 false
@@ -55,9 +66,29 @@ impl ImportManager {
         }
     }
 
-    /// Verifica se un membro specifico è attivo.
-    pub fn is_member_active(&self, member: &str, _parent: &str) -> bool {
-        self.active_members.contains(member)
+    /// Verifica se `member` appartiene al modulo `parent` (es. `send` sta sia in nnet che in nconc).
+    pub fn is_member_of(&self, member: &str, parent: &str) -> bool {
+        self.allowed_imports.get(parent).is_some_and(|members| members.iter().any(|m| m == member))
+    }
+
+    /// Verifica se un membro specifico è stato importato dal modulo genitore indicato.
+    pub fn is_member_active(&self, member: &str, parent: &str) -> bool {
+        self.active_members.contains(&(member.to_string(), parent.to_string()))
+    }
+
+    /// Verifica se un membro è stato importato da un modulo qualsiasi (es. `send` da nnet o da nconc).
+    pub fn is_member_imported(&self, member: &str) -> bool {
+        self.active_members.iter().any(|(m, _)| m == member)
+    }
+
+    /// Lingua principale del programma: la prima importata da "translate".
+    pub fn primary_language(&self) -> Option<&str> {
+        self.imported_languages.first().map(|l| l.as_str())
+    }
+
+    /// Verifica se almeno un membro del modulo genitore è stato importato.
+    pub fn is_parent_active(&self, parent: &str) -> bool {
+        self.active_parents.contains(parent)
     }
 
 
@@ -89,5 +120,23 @@ mod tests {
         assert!(manager2.import_member("sin", "nmath"));
         assert!(manager2.is_member_active("sin", "nmath"));
         assert!(!manager2.is_member_active("cos", "nmath"));
+
+        // La lingua principale è la prima importata
+        let mut manager3 = ImportManager::new();
+        assert_eq!(manager3.primary_language(), None);
+        manager3.import_member("german", "translate");
+        manager3.import_member("*", "translate");
+        assert_eq!(manager3.primary_language(), Some("german"));
+    }
+
+    #[test]
+    fn test_member_active_depends_on_parent() {
+        // `send` sta sia in nnet che in nconc: importarlo da uno non lo attiva dall'altro
+        let mut manager = ImportManager::new();
+        assert!(manager.import_member("send", "nconc"));
+        assert!(manager.is_member_active("send", "nconc"));
+        assert!(!manager.is_member_active("send", "nnet"));
+        assert!(manager.is_member_imported("send"));
+        assert!(!manager.is_member_imported("fetch"));
     }
 }
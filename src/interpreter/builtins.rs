@@ -7,16 +7,41 @@ pub const BUILTIN_NAMES: &[&str] = &[
     // Modulo nio — Input/Output
     "print", "input",
     // Modulo nfs — File System
-    "read", "write", "delete",
+    "read", "write", "delete", "lines",
     // Modulo nmath — Matematica
-    "sin", "cos", "sqrt", "random", "round", "min", "max", "abs", "log", "pow",
-    // Modulo nnet — Rete
+    "sin", "cos", "sqrt", "random", "round", "min", "max", "abs", "log", "pow", "decimal",
+    // Modulo nnet — Rete (`send` è anche in nconc: su un canale accoda il valore)
     "fetch", "send",
+    // Modulo nconc — Task e canali
+    "spawn", "join", "channel", "receive", "select",
     // Funzioni universali (nessun modulo richiesto — sempre disponibili se la lingua è importata)
-    "len", "sleep", "exit",
+    "len", "copy", "set", "push", "split", "sleep", "exit",
+    // Iteratori pigri
+    "range", "take", "skip", "zip", "enumerate", "chain", "next", "collect",
 ];
 
 /// Verifica se un nome corrisponde a una funzione built-in di NodeStract.
 pub fn is_builtin(name: &str) -> bool {
     BUILTIN_NAMES.contains(&name)
 }
+
+/// Verifica se il numero di argomenti è accettato da una funzione built-in.
+/// Restituisce `None` se il nome non corrisponde a un built-in.
+pub fn is_builtin_arity_valid(name: &str, args_count: usize) -> Option<bool> {
+    let valid = match name {
+        "print" => true,
+        "input" => args_count <= 1,
+        "read" | "delete" | "sin" | "cos" | "sqrt" | "abs" | "log" | "decimal" | "sleep" | "fetch" | "len" | "copy" => args_count == 1,
+        "lines" | "enumerate" | "collect" => args_count == 1,
+        "round" | "range" | "next" => args_count == 1 || args_count == 2,
+        "write" | "min" | "max" | "pow" | "send" | "push" | "split" | "take" | "skip" => args_count == 2,
+        "zip" | "chain" => args_count >= 1,
+        "join" | "receive" | "select" => args_count == 1,
+        "spawn" => args_count == 1 || args_count == 2,
+        "channel" => args_count <= 1,
+        "random" => args_count == 0,
+        "exit" | "set" => args_count <= 1,
+        _ => return None,
+    };
+    Some(valid)
+}
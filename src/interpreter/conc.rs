@@ -0,0 +1,386 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use indexmap::IndexMap;
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+use crate::engine::ast::{Expression, Statement};
+use crate::engine::value::{Locale, Value};
+use super::Interpreter;
+
+/// Dimensione dello stack del thread di un task (quella predefinita basta a poche chiamate ricorsive).
+const TASK_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Valore copiato in profondità, che può passare da un task all'altro.
+/// Canali e task restano condivisi: sono il modo in cui i task comunicano.
+#[derive(Clone)]
+pub enum Shared {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    BigInt(BigInt),
+    Float(f64),
+    Decimal(Decimal),
+    String(String),
+    Array(Vec<Shared>),
+    Map(IndexMap<String, Shared>),
+    Set(Vec<Shared>),
+    Record { name: String, fields: IndexMap<String, Shared> },
+    Channel(Arc<Channel>),
+    Task(Arc<Task>),
+}
+
+impl Shared {
+    /// Copia un valore per un altro task. Gli iteratori e le strutture che contengono se stesse non si possono copiare.
+    pub fn from_value(value: &Value) -> Result<Shared, String> {
+        Self::copy_nested(value, &mut Vec::new())
+    }
+
+    /// `open` contiene le strutture in corso di copia, come in `Value::deep_copy`.
+    fn copy_nested(value: &Value, open: &mut Vec<*const ()>) -> Result<Shared, String> {
+        let ptr = value.identity();
+        if let Some(ptr) = ptr {
+            if open.contains(&ptr) {
+                return Err("Runtime Error: A value that contains itself cannot be shared between tasks".to_string());
+            }
+            open.push(ptr);
+        }
+        let shared = match value {
+            Value::Null => Shared::Null,
+            Value::Boolean(b) => Shared::Boolean(*b),
+            Value::Integer(i) => Shared::Integer(*i),
+            Value::BigInt(i) => Shared::BigInt(i.clone()),
+            Value::Float(f) => Shared::Float(*f),
+            Value::Decimal(d) => Shared::Decimal(*d),
+            Value::String(s) => Shared::String(s.clone()),
+            Value::Array(items) => Shared::Array(Self::copy_all(&items.borrow(), open)?),
+            Value::Set(items) => Shared::Set(Self::copy_all(items, open)?),
+            Value::Map(map) => {
+                let mut entries = IndexMap::new();
+                for (k, v) in map.borrow().iter() {
+                    entries.insert(k.clone(), Self::copy_nested(v, open)?);
+                }
+                Shared::Map(entries)
+            }
+            Value::Record(record) => {
+                let record = record.borrow();
+                let mut fields = IndexMap::new();
+                for (k, v) in record.fields.iter() {
+                    fields.insert(k.clone(), Self::copy_nested(v, open)?);
+                }
+                Shared::Record { name: record.name.clone(), fields }
+            }
+            Value::Channel(channel) => Shared::Channel(Arc::clone(channel)),
+            Value::Task(task) => Shared::Task(Arc::clone(task)),
+            Value::Iterator(_) => return Err("Runtime Error: An iterator cannot be shared between tasks; use collect first".to_string()),
+        };
+        if ptr.is_some() {
+            open.pop();
+        }
+        Ok(shared)
+    }
+
+    fn copy_all(items: &[Value], open: &mut Vec<*const ()>) -> Result<Vec<Shared>, String> {
+        items.iter().map(|v| Self::copy_nested(v, open)).collect()
+    }
+
+    /// Ricrea il valore nel task che lo riceve.
+    pub fn into_value(self) -> Value {
+        match self {
+            Shared::Null => Value::Null,
+            Shared::Boolean(b) => Value::Boolean(b),
+            Shared::Integer(i) => Value::Integer(i),
+            Shared::BigInt(i) => Value::BigInt(i),
+            Shared::Float(f) => Value::Float(f),
+            Shared::Decimal(d) => Value::Decimal(d),
+            Shared::String(s) => Value::String(s),
+            Shared::Array(items) => Value::array(items.into_iter().map(Shared::into_value).collect()),
+            Shared::Set(items) => Value::Set(Rc::new(items.into_iter().map(Shared::into_value).collect())),
+            Shared::Map(entries) => Value::map(entries.into_iter().map(|(k, v)| (k, v.into_value())).collect()),
+            Shared::Record { name, fields } => Value::record(name, fields.into_iter().map(|(k, v)| (k, v.into_value())).collect()),
+            Shared::Channel(channel) => Value::Channel(channel),
+            Shared::Task(task) => Value::Task(task),
+        }
+    }
+}
+
+/// Coordina i task di un programma: conta quelli in esecuzione (non in attesa di un canale o di un altro task)
+/// e risveglia chi aspetta quando un valore viene trasmesso o un task termina.
+pub struct Scheduler {
+    state: Mutex<SchedulerState>,
+    wake: Condvar,
+}
+
+struct SchedulerState {
+    running: usize,
+    waiting: usize,
+    // Cresce a ogni risveglio: distingue un risveglio vero da uno spurio
+    round: u64,
+}
+
+impl Scheduler {
+    /// Lo scheduler di un nuovo programma, con il solo task principale in esecuzione.
+    pub fn new() -> Arc<Scheduler> {
+        let state = SchedulerState { running: 1, waiting: 0, round: 0 };
+        Arc::new(Scheduler { state: Mutex::new(state), wake: Condvar::new() })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SchedulerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Attende finché `ready` non restituisce un valore (è chiamata con il lock preso).
+    /// Se tutti gli altri task sono in attesa nessuno potrà più risvegliarlo: l'attesa termina con un errore.
+    fn wait_for<T>(&self, mut ready: impl FnMut() -> Option<T>) -> Result<T, String> {
+        let mut state = self.lock();
+        loop {
+            if let Some(item) = ready() {
+                return Ok(item);
+            }
+            if state.running <= 1 {
+                return Err("Runtime Error: Deadlock: every task is waiting for a value that will never arrive".to_string());
+            }
+            state.running -= 1;
+            state.waiting += 1;
+            let round = state.round;
+            while state.round == round {
+                state = self.wake.wait(state).unwrap_or_else(|e| e.into_inner());
+            }
+        }
+    }
+
+    /// Esegue `change` con il lock preso e risveglia chi aspetta; `delta` aggiorna il numero di task in esecuzione.
+    /// I task risvegliati contano subito come in esecuzione, anche prima di riprendere il lock.
+    fn notify(&self, delta: isize, change: impl FnOnce()) {
+        let mut state = self.lock();
+        state.running = state.running.saturating_add_signed(delta) + state.waiting;
+        state.waiting = 0;
+        state.round += 1;
+        change();
+        self.wake.notify_all();
+    }
+}
+
+/// Coda di valori tra task. Un canale creato con un valore d'esempio accetta solo valori dello stesso tipo.
+pub struct Channel {
+    kind: Option<String>,
+    queue: Mutex<VecDeque<Shared>>,
+}
+
+impl Channel {
+    fn queue(&self) -> MutexGuard<'_, VecDeque<Shared>> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Task avviato con `spawn`: il risultato è il valore restituito o l'eccezione non gestita.
+pub struct Task {
+    outcome: Mutex<Option<Result<Shared, Shared>>>,
+}
+
+impl fmt::Debug for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<channel>")
+    }
+}
+
+impl fmt::Debug for Task {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<task>")
+    }
+}
+
+/// Due canali (o task) sono uguali solo se sono lo stesso oggetto.
+impl PartialEq for Channel {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl PartialEq for Task {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+/// Tipo di un valore per i canali tipizzati: il nome del record per i record.
+fn kind_of(value: &Value) -> String {
+    match value {
+        Value::Record(record) => record.borrow().name.clone(),
+        other => other.type_name().to_string(),
+    }
+}
+
+/// Ciò che serve a un task per eseguire una funzione in un interprete tutto suo.
+struct TaskStart {
+    function: String,
+    args: Vec<Shared>,
+    functions: HashMap<String, Statement>,
+    globals: Vec<(String, Shared, bool)>,
+    locale: Locale,
+    network_send: bool,
+    scheduler: Arc<Scheduler>,
+}
+
+impl TaskStart {
+    fn run(self) -> Result<Shared, Shared> {
+        let mut interpreter = Interpreter::new();
+        interpreter.functions = self.functions;
+        interpreter.locale = self.locale;
+        interpreter.network_send = self.network_send;
+        interpreter.scheduler = self.scheduler;
+        for (name, value, is_mutable) in self.globals {
+            interpreter.define_var(name, value.into_value(), is_mutable);
+        }
+
+        // Gli argomenti, già copiati, arrivano alla funzione tramite variabili temporanee
+        interpreter.enter_scope();
+        let mut call_args = Vec::with_capacity(self.args.len());
+        for (idx, value) in self.args.into_iter().enumerate() {
+            let var_name = format!("$arg{}", idx);
+            interpreter.define_var(var_name.clone(), value.into_value(), false);
+            call_args.push(Expression::Variable(var_name));
+        }
+        let result = interpreter.handle_function_call(&self.function, &call_args);
+        match interpreter.exception.take() {
+            Some(exc) => Err(Shared::from_value(&exc).unwrap_or_else(Shared::String)),
+            None => Shared::from_value(&result).map_err(Shared::String),
+        }
+    }
+}
+
+impl Interpreter {
+    /// Funzioni del modulo nconc: task (`spawn`, `join`) e canali (`channel`, `receive`, `select`).
+    /// Il numero di argomenti è già stato verificato da `is_function_arity_valid`.
+    pub fn handle_conc_call(&mut self, name: &str, args: &[Expression]) -> Value {
+        let values: Vec<Value> = args.iter().map(|arg| self.eval_expression(arg)).collect();
+        if self.exception.is_some() {
+            return Value::Null;
+        }
+        let result = match name {
+            "spawn" => self.spawn_task(&values),
+            "join" => match &values[0] {
+                Value::Task(task) => {
+                    let waited = self.scheduler.wait_for(|| task.outcome.lock().unwrap_or_else(|e| e.into_inner()).clone());
+                    match waited {
+                        Ok(Ok(value)) => Ok(value.into_value()),
+                        // L'eccezione non gestita dal task arriva a chi lo attende
+                        Ok(Err(exc)) => {
+                            self.exception = Some(exc.into_value());
+                            return Value::Null;
+                        }
+                        Err(err_msg) => Err(err_msg),
+                    }
+                }
+                other => Err(format!("TYPE ERROR: join expects a task, got {}", other.type_name())),
+            },
+            "channel" => {
+                let kind = values.first().map(kind_of);
+                Ok(Value::Channel(Arc::new(Channel { kind, queue: Mutex::new(VecDeque::new()) })))
+            }
+            "receive" => match &values[0] {
+                Value::Channel(channel) => self.scheduler.wait_for(|| channel.queue().pop_front()).map(Shared::into_value),
+                other => Err(format!("TYPE ERROR: receive expects a channel, got {}", other.type_name())),
+            },
+            "select" => {
+                let channels: Option<Vec<Arc<Channel>>> = match &values[0] {
+                    Value::Array(items) => items.borrow().iter().map(|item| match item {
+                        Value::Channel(channel) => Some(Arc::clone(channel)),
+                        _ => None,
+                    }).collect(),
+                    _ => None,
+                };
+                match channels {
+                    // Il primo canale (nell'ordine dell'array) che ha un valore pronto
+                    Some(channels) => self.scheduler.wait_for(|| {
+                        channels.iter().enumerate().find_map(|(idx, channel)| channel.queue().pop_front().map(|item| (idx, item)))
+                    }).map(|(idx, item)| Value::array(vec![Value::Integer(idx as i64), item.into_value()])),
+                    None => Err(format!("TYPE ERROR: select expects an array of channels, got {}", values[0].type_name())),
+                }
+            }
+            _ => Ok(Value::Null),
+        };
+        match result {
+            Ok(value) => value,
+            Err(err_msg) => {
+                if self.exception.is_none() {
+                    self.exception = Some(Value::String(err_msg));
+                }
+                Value::Null
+            }
+        }
+    }
+
+    /// `send` su un canale: accoda una copia del valore, rispettando il tipo del canale.
+    pub fn send_to_channel(&mut self, channel: &Arc<Channel>, value: &Value) -> Result<Value, String> {
+        match &channel.kind {
+            Some(kind) if *kind != kind_of(value) => {
+                Err(format!("TYPE ERROR: Channel of {} cannot carry a value of type {}", kind, kind_of(value)))
+            }
+            _ => Shared::from_value(value).map(|item| {
+                self.scheduler.notify(0, || channel.queue().push_back(item));
+                Value::Null
+            }),
+        }
+    }
+
+    /// Avvia `function` con gli argomenti dell'array su un thread separato. Il task vede le funzioni del programma
+    /// e una copia delle variabili globali; le sue modifiche non sono visibili agli altri task.
+    fn spawn_task(&mut self, values: &[Value]) -> Result<Value, String> {
+        let function = match &values[0] {
+            Value::String(s) => s.clone(),
+            other => return Err(format!("TYPE ERROR: spawn expects a function, got {}", other.type_name())),
+        };
+        let args = match values.get(1) {
+            None => Vec::new(),
+            Some(Value::Array(items)) => items.borrow().iter().map(Shared::from_value).collect::<Result<Vec<_>, _>>()?,
+            Some(other) => return Err(format!("TYPE ERROR: spawn expects an array of arguments, got {}", other.type_name())),
+        };
+        if !self.is_function_defined(&function) {
+            return Err(format!("Runtime Error: Function '{}' is not defined", function));
+        }
+        if !self.is_function_arity_valid(&function, args.len()) {
+            return Err(format!(
+                "Arity Mismatch: Function '{}' expects different number of arguments (provided {})",
+                function, args.len()
+            ));
+        }
+        // Le globali che non si possono copiare (es. iteratori) restano fuori dal task
+        let globals = self.scopes[0].iter()
+            .filter_map(|(name, entry)| Shared::from_value(&entry.value).ok().map(|value| (name.clone(), value, entry.is_mutable)))
+            .collect();
+        let start = TaskStart {
+            function,
+            args,
+            functions: self.functions.clone(),
+            globals,
+            locale: self.locale.clone(),
+            network_send: self.network_send,
+            scheduler: Arc::clone(&self.scheduler),
+        };
+
+        let task = Arc::new(Task { outcome: Mutex::new(None) });
+        let handle = Arc::clone(&task);
+        let scheduler = Arc::clone(&self.scheduler);
+        scheduler.notify(1, || {});
+        let spawned = thread::Builder::new().stack_size(TASK_STACK_SIZE).spawn(move || {
+            // Anche se il task va in panic lo scheduler deve contarlo come terminato, altrimenti chi lo attende
+            // resta bloccato: il panic diventa l'eccezione che `join` riporta
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| start.run())).unwrap_or_else(|payload| {
+                let reason = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                Err(Shared::String(format!("Runtime Error: The task stopped unexpectedly. {}", reason)))
+            });
+            scheduler.notify(-1, || *handle.outcome.lock().unwrap_or_else(|e| e.into_inner()) = Some(outcome));
+        });
+        if let Err(e) = spawned {
+            self.scheduler.notify(-1, || {});
+            return Err(format!("Runtime Error: Could not start the task. {}", e));
+        }
+        Ok(Value::Task(task))
+    }
+}
@@ -9,26 +9,37 @@ impl Interpreter {
         }
         match expr {
             Expression::LiteralStr(s) => Value::String(s.clone()),
-            Expression::LiteralNum(n) => {
-                if n.fract() == 0.0 {
-                    Value::Integer(*n as i64)
-                } else {
-                    Value::Float(*n)
+            Expression::Template(parts) => {
+                let mut text = String::new();
+                for part in parts {
+                    let val = self.eval_expression(part);
+                    text.push_str(&val.display(&self.locale));
                 }
+                Value::String(text)
             }
+            Expression::LiteralInt(n) => Value::Integer(*n),
+            Expression::LiteralBigInt(n) => Value::BigInt(n.clone()),
+            Expression::LiteralFloat(n) => Value::Float(*n),
             Expression::LiteralBool(b) => Value::Boolean(*b),
             Expression::LiteralNull => Value::Null,
             Expression::Array(elements) => {
                 let vals: Vec<Value> = elements.iter().map(|e| self.eval_expression(e)).collect();
-                Value::Array(vals)
+                Value::array(vals)
             }
             Expression::Map(pairs) => {
-                let mut map = std::collections::HashMap::new();
+                let mut map = indexmap::IndexMap::new();
                 for (k, v_expr) in pairs {
                     let val = self.eval_expression(v_expr);
                     map.insert(k.clone(), val);
                 }
-                Value::Map(map)
+                Value::map(map)
+            }
+            Expression::Set(elements) => {
+                let vals: Vec<Value> = elements.iter().map(|e| self.eval_expression(e)).collect();
+                Value::set(vals).unwrap_or_else(|err_msg| {
+                    self.exception = Some(Value::String(err_msg));
+                    Value::Null
+                })
             }
             Expression::Variable(name) => {
                 if !self.has_var(name) {
@@ -46,30 +57,20 @@ impl Interpreter {
             Expression::Index { target, index } => {
                 let target_val = self.eval_expression(target);
                 let index_val = self.eval_expression(index);
-
-                match target_val {
-                    Value::Array(arr) => {
-                        let idx = match index_val {
-                            Value::Integer(i) => i as usize,
-                            Value::Float(f) => f as usize,
-                            _ => return Value::Null,
-                        };
-                        if idx < arr.len() {
-                            return arr[idx].clone();
-                        }
-                    }
-                    Value::Map(map) => {
-                        let key = index_val.to_string();
-                        if let Some(val) = map.get(&key) {
-                            return val.clone();
-                        }
-                    }
-                    _ => {}
-                }
-                Value::Null
+                self.index_value(target_val, index_val, false)
+            }
+            Expression::OptionalIndex { target, index } => {
+                let target_val = self.eval_expression(target);
+                let index_val = self.eval_expression(index);
+                self.index_value(target_val, index_val, true)
             }
             Expression::BinaryOp { left, operator, right } => {
-                if operator == "&&" {
+                if operator == "??" {
+                    match self.eval_expression(left) {
+                        Value::Null => self.eval_expression(right),
+                        l => l,
+                    }
+                } else if operator == "&&" {
                     let l = self.eval_expression(left);
                     if !l.is_truthy() {
                         Value::Boolean(false)
@@ -96,8 +97,10 @@ impl Interpreter {
                 match operator.as_str() {
                     "!" => Value::Boolean(!val.is_truthy()),
                     "-" => match val {
-                        Value::Integer(i) => Value::Integer(-i),
+                        Value::Integer(i) => i.checked_neg().map(Value::Integer).unwrap_or_else(|| Value::from_bigint(-num_bigint::BigInt::from(i))),
+                        Value::BigInt(i) => Value::from_bigint(-i),
                         Value::Float(f) => Value::Float(-f),
+                        Value::Decimal(d) => Value::Decimal(-d),
                         _ => Value::Null,
                     },
                     _ => Value::Null,
@@ -158,6 +161,64 @@ impl Interpreter {
                 };
                 self.handle_function_call(&func_name, args)
             }
+            Expression::MethodCall { receiver, method, args } => {
+                let receiver_val = self.eval_expression(receiver);
+                self.handle_method_call(receiver_val, method, args)
+            }
+            Expression::NamedArg { value, .. } => self.eval_expression(value),
+            Expression::Match { subject, arms, default } => self.eval_match(subject, arms, default.as_deref()),
+        }
+    }
+
+    /// Legge un elemento di un array, una chiave di una mappa o un campo di un record.
+    /// Una chiave assente, un indice fuori dai limiti o un valore null sono errori,
+    /// a meno che l'accesso sia facoltativo (`?.`): in quel caso il risultato è null.
+    fn index_value(&mut self, target: Value, index: Value, optional: bool) -> Value {
+        if self.exception.is_some() {
+            return Value::Null;
+        }
+        let err_msg = match target {
+            Value::Array(arr) => {
+                let idx = match index {
+                    Value::Integer(i) => i,
+                    Value::Float(f) if f.fract() == 0.0 => f as i64,
+                    other => {
+                        let err_msg = format!("Runtime Error: Array index must be an integer, found {}", other.type_name());
+                        self.exception = Some(Value::String(err_msg));
+                        return Value::Null;
+                    }
+                };
+                let arr = arr.borrow();
+                match usize::try_from(idx).ok().and_then(|i| arr.get(i)) {
+                    Some(val) => return val.clone(),
+                    None => format!("Runtime Error: Index {} out of range for array of length {}", idx, arr.len()),
+                }
+            }
+            Value::Map(map) => {
+                let key = index.to_string();
+                match map.borrow().get(&key) {
+                    Some(val) => return val.clone(),
+                    None => format!("Runtime Error: Key '{}' not found in map", key),
+                }
+            }
+            Value::Record(record) => {
+                let record = record.borrow();
+                let key = index.to_string();
+                match record.fields.get(&key) {
+                    Some(val) => return val.clone(),
+                    None => record.unknown_field(&key),
+                }
+            }
+            Value::Null => format!("Runtime Error: Cannot read '{}' of null", index.display(&self.locale)),
+            other => {
+                let err_msg = format!("Runtime Error: Cannot index a value of type {}", other.type_name());
+                self.exception = Some(Value::String(err_msg));
+                return Value::Null;
+            }
+        };
+        if !optional {
+            self.exception = Some(Value::String(err_msg));
         }
+        Value::Null
     }
-}
\ No newline at end of file
+}
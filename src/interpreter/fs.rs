@@ -1,12 +1,29 @@
-use std::fs;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Lines};
 use std::path::Path;
+use std::str::FromStr;
+use num_bigint::BigInt;
 use crate::engine::value::Value;
 
-fn ns_to_serde(val: &Value) -> serde_json::Value {
-    match val {
+/// Converte un valore in JSON. `open` contiene le strutture in corso di scrittura:
+/// una struttura che contiene se stessa non ha una forma JSON finita ed è un errore.
+fn ns_to_serde(val: &Value, open: &mut Vec<*const ()>) -> Result<serde_json::Value, String> {
+    let ptr = val.identity();
+    if let Some(ptr) = ptr {
+        if open.contains(&ptr) {
+            return Err(format!("FS Error: Cannot write a cyclic value ({} containing itself) as JSON.", val.type_name()));
+        }
+        open.push(ptr);
+    }
+    let json = match val {
         Value::Null => serde_json::Value::Null,
         Value::Boolean(b) => serde_json::Value::Bool(*b),
         Value::Integer(i) => serde_json::Value::Number((*i).into()),
+        // Interi grandi e decimali vengono scritti con tutte le loro cifre
+        Value::BigInt(_) | Value::Decimal(_) => match serde_json::Number::from_str(&val.to_string()) {
+            Ok(num) => serde_json::Value::Number(num),
+            Err(_) => serde_json::Value::Null,
+        },
         Value::Float(f) => {
             if let Some(num) = serde_json::Number::from_f64(*f) {
                 serde_json::Value::Number(num)
@@ -16,16 +33,32 @@ fn ns_to_serde(val: &Value) -> serde_json::Value {
         }
         Value::String(s) => serde_json::Value::String(s.clone()),
         Value::Array(arr) => {
-            serde_json::Value::Array(arr.iter().map(ns_to_serde).collect())
+            serde_json::Value::Array(arr.borrow().iter().map(|v| ns_to_serde(v, open)).collect::<Result<_, _>>()?)
+        }
+        // JSON non ha insiemi: vengono scritti come array
+        Value::Set(items) => serde_json::Value::Array(items.iter().map(|v| ns_to_serde(v, open)).collect::<Result<_, _>>()?),
+        // Un record diventa un oggetto con i suoi campi (riletto, sarà una mappa)
+        Value::Record(record) => {
+            let mut obj = serde_json::Map::new();
+            for (k, v) in record.borrow().fields.iter() {
+                obj.insert(k.clone(), ns_to_serde(v, open)?);
+            }
+            serde_json::Value::Object(obj)
         }
+        // Un iteratore non ha una rappresentazione JSON (va prima raccolto con `collect`), né canali e task
+        Value::Iterator(_) | Value::Channel(_) | Value::Task(_) => serde_json::Value::Null,
         Value::Map(map) => {
             let mut obj = serde_json::Map::new();
-            for (k, v) in map {
-                obj.insert(k.clone(), ns_to_serde(v));
+            for (k, v) in map.borrow().iter() {
+                obj.insert(k.clone(), ns_to_serde(v, open)?);
             }
             serde_json::Value::Object(obj)
         }
+    };
+    if ptr.is_some() {
+        open.pop();
     }
+    Ok(json)
 }
 
 fn serde_to_ns(val: serde_json::Value) -> Value {
@@ -35,6 +68,8 @@ fn serde_to_ns(val: serde_json::Value) -> Value {
         serde_json::Value::Number(n) => {
             if let Some(i) = n.as_i64() {
                 Value::Integer(i)
+            } else if let Ok(i) = BigInt::from_str(&n.to_string()) {
+                Value::BigInt(i)
             } else if let Some(f) = n.as_f64() {
                 Value::Float(f)
             } else {
@@ -43,14 +78,14 @@ fn serde_to_ns(val: serde_json::Value) -> Value {
         }
         serde_json::Value::String(s) => Value::String(s),
         serde_json::Value::Array(arr) => {
-            Value::Array(arr.into_iter().map(serde_to_ns).collect())
+            Value::array(arr.into_iter().map(serde_to_ns).collect())
         }
         serde_json::Value::Object(obj) => {
-            let mut map = std::collections::HashMap::new();
+            let mut map = indexmap::IndexMap::new();
             for (k, v) in obj {
                 map.insert(k, serde_to_ns(v));
             }
-            Value::Map(map)
+            Value::map(map)
         }
     }
 }
@@ -76,6 +111,17 @@ pub fn read_file(path: &str) -> Result<Value, String> {
     }
 }
 
+/// Apre un file di testo per leggerne le righe una alla volta (funzione `lines`).
+pub fn read_lines(path: &str) -> Result<Lines<BufReader<File>>, String> {
+    if !path.ends_with(".txt") {
+        return Err("FS Error: Only .txt files can be read line by line.".to_string());
+    }
+    match File::open(path) {
+        Ok(file) => Ok(BufReader::new(file).lines()),
+        Err(e) => Err(format!("FS Error: Could not read file '{}'. {}", path, e)),
+    }
+}
+
 pub fn write_file(path: &str, content: &Value) -> Result<Value, String> {
     if !path.ends_with(".json") && !path.ends_with(".txt") {
         return Err("FS Error: Only .json or .txt files allowed.".to_string());
@@ -88,7 +134,7 @@ pub fn write_file(path: &str, content: &Value) -> Result<Value, String> {
     }
 
     let write_res = if path.ends_with(".json") {
-        let serde_val = ns_to_serde(content);
+        let serde_val = ns_to_serde(content, &mut Vec::new())?;
         serde_json::to_string_pretty(&serde_val).map_err(|e| e.to_string())
     } else {
         Ok(content.to_string())
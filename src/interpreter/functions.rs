@@ -1,12 +1,17 @@
 use crate::engine::ast::{Statement, Expression};
 use crate::engine::value::Value;
-use super::{Interpreter, VarEntry};
+use super::Interpreter;
 use std::collections::HashMap;
 use std::io::{self, Write};
 use std::thread;
 use std::time::Duration;
 use super::fs;
 use super::net;
+use std::str::FromStr;
+use num_bigint::BigInt;
+use num_traits::Signed;
+use rust_decimal::{Decimal, RoundingStrategy};
+use indexmap::IndexMap;
 
 impl Interpreter {
     pub fn handle_function_call(&mut self, target: &str, args: &Vec<Expression>) -> Value {
@@ -23,6 +28,11 @@ impl Interpreter {
             self.exception = Some(Value::String(err_msg));
             return Value::Null;
         }
+        if super::builtins::is_builtin(target) && args.iter().any(|arg| matches!(arg, Expression::NamedArg { .. })) {
+            let err_msg = format!("Runtime Error: Built-in function '{}' does not accept named arguments", target);
+            self.exception = Some(Value::String(err_msg));
+            return Value::Null;
+        }
 
         match target {
             // Operazioni di I/O
@@ -30,16 +40,16 @@ impl Interpreter {
                 let mut output = String::new();
                 for a in args {
                     let val = self.eval_expression(a);
-                    output.push_str(&val.to_string());
+                    output.push_str(&val.print_text(&self.locale));
                 }
                 println!("{}", output);
                 let _ = io::stdout().flush();
                 Value::Null
             }
             "input" => {
-                if let Some(prompt_expr) = args.get(0) {
+                if let Some(prompt_expr) = args.first() {
                     let raw_prompt = self.eval_expression(prompt_expr);
-                    print!("{}", raw_prompt);
+                    print!("{}", raw_prompt.print_text(&self.locale));
                     let _ = io::stdout().flush();
                 }
                 let mut buffer = String::new();
@@ -52,7 +62,7 @@ impl Interpreter {
 
             // Operazioni su File System
             "read" => {
-                if let Some(path_expr) = args.get(0) {
+                if let Some(path_expr) = args.first() {
                     let path_val = self.eval_expression(path_expr);
                     match fs::read_file(&path_val.to_string()) {
                         Ok(val) => val,
@@ -80,8 +90,21 @@ impl Interpreter {
                     Value::Boolean(false)
                 }
             }
+            "lines" => {
+                let path_val = self.eval_expression(&args[0]);
+                if self.exception.is_some() {
+                    return Value::Null;
+                }
+                match fs::read_lines(&path_val.to_string()) {
+                    Ok(lines) => super::iterators::iter_value(super::iterators::Iter::Lines(lines)),
+                    Err(e) => {
+                        self.exception = Some(Value::String(e));
+                        Value::Null
+                    }
+                }
+            }
             "delete" => {
-                if let Some(path_expr) = args.get(0) {
+                if let Some(path_expr) = args.first() {
                     let path_val = self.eval_expression(path_expr);
                     match fs::delete_file(&path_val.to_string()) {
                         Ok(val) => val,
@@ -97,40 +120,25 @@ impl Interpreter {
 
             // Operazioni matematiche
             "sin" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
-                    let num = match val {
-                        Value::Integer(i) => i as f64,
-                        Value::Float(f) => f,
-                        _ => 0.0,
-                    };
-                    Value::Float(num.sin())
+                    Value::Float(val.as_f64().unwrap_or(0.0).sin())
                 } else {
                     Value::Null
                 }
             }
             "cos" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
-                    let num = match val {
-                        Value::Integer(i) => i as f64,
-                        Value::Float(f) => f,
-                        _ => 0.0,
-                    };
-                    Value::Float(num.cos())
+                    Value::Float(val.as_f64().unwrap_or(0.0).cos())
                 } else {
                     Value::Null
                 }
             }
             "sqrt" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
-                    let num = match val {
-                        Value::Integer(i) => i as f64,
-                        Value::Float(f) => f,
-                        _ => 0.0,
-                    };
-                    Value::Float(num.sqrt())
+                    Value::Float(val.as_f64().unwrap_or(0.0).sqrt())
                 } else {
                     Value::Null
                 }
@@ -139,42 +147,48 @@ impl Interpreter {
                 Value::Float(fastrand::f64())
             }
             "round" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
-                    let num = match val {
-                        Value::Integer(i) => return Value::Integer(i),
-                        Value::Float(f) => f.round() as i64,
-                        _ => 0,
+                    // Secondo argomento opzionale: numero di cifre decimali da mantenere
+                    let digits = match args.get(1).map(|d| self.eval_expression(d)) {
+                        Some(Value::Integer(d)) => Some(d.clamp(0, 28) as u32),
+                        _ => None,
                     };
-                    Value::Integer(num)
-                } else {
-                    Value::Null
-                }
-            }
-            "min" => {
-                if args.len() >= 2 {
-                    let l = self.eval_expression(&args[0]);
-                    let r = self.eval_expression(&args[1]);
-                    match (l, r) {
-                        (Value::Integer(a), Value::Integer(b)) => Value::Integer(a.min(b)),
-                        (Value::Float(a), Value::Float(b)) => Value::Float(a.min(b)),
-                        (Value::Integer(a), Value::Float(b)) => Value::Float((a as f64).min(b)),
-                        (Value::Float(a), Value::Integer(b)) => Value::Float(a.min(b as f64)),
-                        _ => Value::Null,
+                    match (val, digits) {
+                        (Value::Float(f), None) => Value::Integer(f.round() as i64),
+                        (Value::Float(f), Some(d)) => {
+                            let factor = 10f64.powi(d as i32);
+                            Value::Float((f * factor).round() / factor)
+                        }
+                        (Value::Decimal(x), digits) => {
+                            let rounded = x.round_dp_with_strategy(digits.unwrap_or(0), RoundingStrategy::MidpointAwayFromZero);
+                            match digits {
+                                Some(_) => Value::Decimal(rounded),
+                                None => BigInt::from_str(&rounded.trunc().to_string()).map(Value::from_bigint).unwrap_or(Value::Null),
+                            }
+                        }
+                        (i @ (Value::Integer(_) | Value::BigInt(_)), _) => i,
+                        _ => Value::Integer(0),
                     }
                 } else {
                     Value::Null
                 }
             }
-            "max" => {
+            "min" | "max" => {
                 if args.len() >= 2 {
                     let l = self.eval_expression(&args[0]);
                     let r = self.eval_expression(&args[1]);
+                    let is_min = target == "min";
                     match (l, r) {
-                        (Value::Integer(a), Value::Integer(b)) => Value::Integer(a.max(b)),
-                        (Value::Float(a), Value::Float(b)) => Value::Float(a.max(b)),
-                        (Value::Integer(a), Value::Float(b)) => Value::Float((a as f64).max(b)),
-                        (Value::Float(a), Value::Integer(b)) => Value::Float(a.max(b as f64)),
+                        (Value::Integer(a), Value::Integer(b)) => Value::Integer(if is_min { a.min(b) } else { a.max(b) }),
+                        (Value::Float(a), Value::Float(b)) => Value::Float(if is_min { a.min(b) } else { a.max(b) }),
+                        (Value::Integer(a), Value::Float(b)) => Value::Float(if is_min { (a as f64).min(b) } else { (a as f64).max(b) }),
+                        (Value::Float(a), Value::Integer(b)) => Value::Float(if is_min { a.min(b as f64) } else { a.max(b as f64) }),
+                        // Interi grandi e decimali: vince il valore stesso, senza conversioni
+                        (l, r) if l.as_f64().is_some() && r.as_f64().is_some() => {
+                            let left_first = self.eval_binary_op(l.clone(), if is_min { "<=" } else { ">=" }, r.clone());
+                            if left_first.is_truthy() { l } else { r }
+                        }
                         _ => Value::Null,
                     }
                 } else {
@@ -182,11 +196,13 @@ impl Interpreter {
                 }
             }
             "abs" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
                     match val {
-                        Value::Integer(i) => Value::Integer(i.abs()),
+                        Value::Integer(i) => i.checked_abs().map(Value::Integer).unwrap_or_else(|| Value::from_bigint(BigInt::from(i).abs())),
+                        Value::BigInt(i) => Value::from_bigint(i.abs()),
                         Value::Float(f) => Value::Float(f.abs()),
+                        Value::Decimal(d) => Value::Decimal(d.abs()),
                         _ => Value::Null,
                     }
                 } else {
@@ -194,14 +210,9 @@ impl Interpreter {
                 }
             }
             "log" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
-                    let num = match val {
-                        Value::Integer(i) => i as f64,
-                        Value::Float(f) => f,
-                        _ => 0.0,
-                    };
-                    Value::Float(num.ln())
+                    Value::Float(val.as_f64().unwrap_or(0.0).ln())
                 } else {
                     Value::Null
                 }
@@ -210,17 +221,58 @@ impl Interpreter {
                 if args.len() >= 2 {
                     let base_val = self.eval_expression(&args[0]);
                     let exponent_val = self.eval_expression(&args[1]);
-                    let base = match base_val {
-                        Value::Integer(i) => i as f64,
-                        Value::Float(f) => f,
-                        _ => 0.0,
-                    };
+                    // Con esponente intero non negativo interi e decimali restano esatti
                     let exponent = match exponent_val {
-                        Value::Integer(i) => i as f64,
-                        Value::Float(f) => f,
-                        _ => 0.0,
+                        Value::Integer(e) => u32::try_from(e).ok(),
+                        _ => None,
+                    };
+                    let integer_base = match &base_val {
+                        Value::Integer(b) => Some(BigInt::from(*b)),
+                        Value::BigInt(b) => Some(b.clone()),
+                        _ => None,
                     };
-                    Value::Float(base.powf(exponent))
+                    match (base_val, exponent) {
+                        (_, Some(e)) if integer_base.is_some() => {
+                            let base = integer_base.unwrap_or_default();
+                            // Limita la dimensione del risultato (circa 300.000 cifre)
+                            if base.bits().saturating_mul(u64::from(e)) > MAX_POW_BITS {
+                                self.exception = Some(Value::String("MATH ERROR: Result of 'pow' is too large.".to_string()));
+                                return Value::Null;
+                            }
+                            Value::from_bigint(base.pow(e))
+                        }
+                        (Value::Decimal(b), Some(e)) => match decimal_pow(b, e) {
+                            Some(d) => Value::Decimal(d),
+                            None => {
+                                self.exception = Some(Value::String("MATH ERROR: Decimal overflow.".to_string()));
+                                Value::Null
+                            }
+                        },
+                        (base_val, _) => {
+                            let base = base_val.as_f64().unwrap_or(0.0);
+                            Value::Float(base.powf(exponent_val.as_f64().unwrap_or(0.0)))
+                        }
+                    }
+                } else {
+                    Value::Null
+                }
+            }
+            "decimal" => {
+                if let Some(arg) = args.first() {
+                    let val = self.eval_expression(arg);
+                    // Le stringhe vengono lette in modo esatto (es. decimal("0.1"))
+                    let decimal = match &val {
+                        Value::String(s) => Decimal::from_str(s.trim()).ok(),
+                        other => other.as_decimal(),
+                    };
+                    match decimal {
+                        Some(d) => Value::Decimal(d),
+                        None => {
+                            let err_msg = format!("MATH ERROR: Cannot convert '{}' to a decimal.", val);
+                            self.exception = Some(Value::String(err_msg));
+                            Value::Null
+                        }
+                    }
                 } else {
                     Value::Null
                 }
@@ -228,20 +280,85 @@ impl Interpreter {
 
             // Utility generali
             "len" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
                     match val {
-                        Value::Array(arr) => Value::Integer(arr.len() as i64),
+                        Value::Array(arr) => Value::Integer(arr.borrow().len() as i64),
                         Value::String(s) => Value::Integer(s.len() as i64),
-                        Value::Map(m) => Value::Integer(m.len() as i64),
+                        Value::Map(m) => Value::Integer(m.borrow().len() as i64),
+                        Value::Set(s) => Value::Integer(s.len() as i64),
                         _ => Value::Integer(0),
                     }
                 } else {
                     Value::Integer(0)
                 }
             }
+            "copy" => {
+                let Some(arg) = args.first() else {
+                    return Value::Null;
+                };
+                self.eval_expression(arg).deep_copy().unwrap_or_else(|err_msg| {
+                    self.exception = Some(Value::String(err_msg));
+                    Value::Null
+                })
+            }
+            "set" => {
+                let Some(arg) = args.first() else {
+                    return Value::Set(Default::default());
+                };
+                // Gli elementi di una collezione (array, chiavi di una mappa, caratteri) senza ripetizioni
+                let val = self.eval_expression(arg);
+                if self.exception.is_some() {
+                    return Value::Null;
+                }
+                match val.elements().and_then(Value::set) {
+                    Ok(set) => set,
+                    Err(err_msg) => {
+                        self.exception = Some(Value::String(err_msg));
+                        Value::Null
+                    }
+                }
+            }
+            "push" => {
+                let list = self.eval_expression(&args[0]);
+                let item = self.eval_expression(&args[1]);
+                match list {
+                    Value::Array(items) => items.borrow_mut().push(item),
+                    other => {
+                        if self.exception.is_none() {
+                            let err_msg = format!("TYPE ERROR: Cannot push onto {}", other.type_name());
+                            self.exception = Some(Value::String(err_msg));
+                        }
+                    }
+                }
+                Value::Null
+            }
+            "split" => {
+                let text = self.eval_expression(&args[0]);
+                let separator = self.eval_expression(&args[1]);
+                let result = match (&text, &separator) {
+                    (Value::String(_), Value::String(sep)) if sep.is_empty() => {
+                        Err("TYPE ERROR: split separator must not be empty".to_string())
+                    }
+                    (Value::String(text), Value::String(sep)) => {
+                        Ok(Value::array(text.split(sep.as_str()).map(|part| Value::String(part.to_string())).collect()))
+                    }
+                    _ => Err(format!("TYPE ERROR: split expects two strings, got {} and {}", text.type_name(), separator.type_name())),
+                };
+                match result {
+                    Ok(parts) => parts,
+                    Err(err_msg) => {
+                        if self.exception.is_none() {
+                            self.exception = Some(Value::String(err_msg));
+                        }
+                        Value::Null
+                    }
+                }
+            }
+            "range" | "take" | "skip" | "zip" | "enumerate" | "chain" | "next" | "collect" => self.handle_iterator_call(target, args),
+            "spawn" | "join" | "channel" | "receive" | "select" => self.handle_conc_call(target, args),
             "sleep" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
                     let secs = match val {
                         Value::Integer(i) => i as u64,
@@ -253,7 +370,7 @@ impl Interpreter {
                 Value::Null
             }
             "exit" => {
-                if let Some(arg) = args.get(0) {
+                if let Some(arg) = args.first() {
                     let val = self.eval_expression(arg);
                     let code = match val {
                         Value::Integer(i) => i as i32,
@@ -267,7 +384,7 @@ impl Interpreter {
 
             // Operazioni di rete
             "fetch" => {
-                if let Some(url_expr) = args.get(0) {
+                if let Some(url_expr) = args.first() {
                     let url_val = self.eval_expression(url_expr);
                     match net::get(&url_val.to_string()) {
                         Ok(val) => val,
@@ -284,7 +401,16 @@ impl Interpreter {
                 if args.len() >= 2 {
                     let url_val = self.eval_expression(&args[0]);
                     let body_val = self.eval_expression(&args[1]);
-                    match net::post(&url_val.to_string(), &body_val.to_string()) {
+                    if self.exception.is_some() {
+                        return Value::Null;
+                    }
+                    // Su un canale (nconc) accoda il valore; su un URL è una richiesta POST, solo se nnet è importato
+                    let sent = match &url_val {
+                        Value::Channel(channel) => self.send_to_channel(channel, &body_val),
+                        _ if self.network_send => net::post(&url_val.to_string(), &body_val.to_string()),
+                        other => Err(format!("TYPE ERROR: send expects a channel, got {}", other.type_name())),
+                    };
+                    match sent {
                         Ok(val) => val,
                         Err(e) => {
                             self.exception = Some(Value::String(e));
@@ -297,36 +423,176 @@ impl Interpreter {
             }
 
             _ => {
-                if let Some(func_stmt) = self.functions.get(target).cloned() {
-                    if let Statement::FunctionDecl { params, body, .. } = func_stmt {
-                        let mut new_scope = HashMap::new();
-                        for (i, param_name) in params.iter().enumerate() {
-                            let arg_val = self.eval_expression(&args[i]);
-                            let entry = VarEntry { value: arg_val, is_mutable: true };
-                            new_scope.insert(param_name.clone(), entry);
-                        }
+                if let Some(Statement::RecordDecl { name, fields, .. }) = self.functions.get(target).cloned() {
+                    return self.construct_record(name, &fields, args);
+                }
+                if let Some(Statement::FunctionDecl { params, rest, body, is_generator, .. }) = self.functions.get(target).cloned() {
+                    let Some((values, extra)) = self.collect_arguments("Function", target, &params, args) else {
+                        return Value::Null;
+                    };
 
-                        let scope_idx = self.scopes.len();
-                        self.scopes.push(new_scope);
-                        self.fn_scope_starts.push(scope_idx);
+                    let scope_idx = self.scopes.len();
+                    self.scopes.push(HashMap::new());
+                    self.fn_scope_starts.push(scope_idx);
 
-                        for s in body {
-                            self.execute_statement(&s);
+                    // I valori predefiniti sono calcolati nello scope della funzione, dove vedono i parametri precedenti
+                    for ((param, default), value) in params.iter().zip(values) {
+                        let value = match (value, default) {
+                            (Some(value), _) => value,
+                            (None, Some(default)) => self.eval_expression(default),
+                            (None, None) => Value::Null,
+                        };
+                        self.define_var(param.clone(), value, true);
+                    }
+                    if let Some(rest) = rest {
+                        self.define_var(rest, Value::array(extra), true);
+                    }
+
+                    if is_generator {
+                        // Il corpo di un generatore parte alla prima richiesta di un valore
+                        let generator = self.create_generator(scope_idx, &body);
+                        self.fn_scope_starts.pop();
+                        return if self.exception.is_some() { Value::Null } else { generator };
+                    }
+                    if self.exception.is_none() {
+                        self.hoist_functions(&body);
+                        for s in body.iter() {
+                            self.execute_statement(s);
                             if self.last_return.is_some() || self.exception.is_some() {
                                 break;
                             }
                         }
+                    }
 
-                        self.fn_scope_starts.pop();
-                        self.scopes.truncate(scope_idx);
+                    self.fn_scope_starts.pop();
+                    self.scopes.truncate(scope_idx);
 
-                        let result = self.last_return.clone().unwrap_or(Value::Null);
-                        self.last_return = None;
-                        return result;
-                    }
+                    let result = self.last_return.clone().unwrap_or(Value::Null);
+                    self.last_return = None;
+                    return result;
                 }
                 Value::Null
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Chiama `receiver.method(args)`. Se il valore è una mappa (o un record) con una chiave `method`
+    /// vale la chiamata dinamica tramite il nome salvato; altrimenti `method` è un built-in o una
+    /// funzione dell'utente che riceve il valore come primo argomento.
+    pub fn handle_method_call(&mut self, receiver: Value, method: &str, args: &[Expression]) -> Value {
+        if self.exception.is_some() {
+            return Value::Null;
+        }
+        let stored = match &receiver {
+            Value::Map(map) => map.borrow().get(method).cloned(),
+            Value::Record(record) => record.borrow().fields.get(method).cloned(),
+            _ => None,
+        };
+        // La chiave viene chiamata solo se contiene il nome di una funzione; altrimenti vale il metodo omonimo
+        match stored {
+            Some(Value::String(name)) if self.is_function_defined(&name) => {
+                return self.handle_function_call(&name, &args.to_vec());
+            }
+            Some(other) if !self.is_function_defined(method) => {
+                let err_msg = format!("Runtime Error: Key '{}' is not callable ({})", method, other.type_name());
+                self.exception = Some(Value::String(err_msg));
+                return Value::Null;
+            }
+            _ => {}
+        }
+        if !self.is_function_defined(method) {
+            let err_msg = format!("Runtime Error: Method '{}' is not defined for {}", method, receiver.type_name());
+            self.exception = Some(Value::String(err_msg));
+            return Value::Null;
+        }
+
+        // Il valore già calcolato viene passato tramite una variabile temporanea in uno scope dedicato
+        let mut call_args = vec![Expression::Variable(RECEIVER_VAR.to_string())];
+        call_args.extend(args.iter().cloned());
+        self.enter_scope();
+        self.define_var(RECEIVER_VAR.to_string(), receiver, false);
+        let result = self.handle_function_call(method, &call_args);
+        self.exit_scope();
+        result
+    }
+
+    /// Crea un'istanza di record: gli argomenti riempiono i campi in ordine o per nome, quelli mancanti
+    /// prendono il valore predefinito (valutato a ogni costruzione, così da non condividere array e mappe).
+    fn construct_record(&mut self, name: String, fields: &[(String, Option<Expression>)], args: &[Expression]) -> Value {
+        let Some((provided, _)) = self.collect_arguments("Record", &name, fields, args) else {
+            return Value::Null;
+        };
+        let mut values = IndexMap::new();
+        for ((field, default), val) in fields.iter().zip(provided) {
+            let val = match (val, default) {
+                (Some(val), _) => val,
+                (None, Some(default)) => self.eval_expression(default),
+                (None, None) => Value::Null,
+            };
+            values.insert(field.clone(), val);
+        }
+        Value::record(name, values)
+    }
+
+    /// Valuta gli argomenti di una chiamata e li associa ai parametri: prima quelli posizionali, poi quelli con nome.
+    /// Restituisce il valore di ogni parametro (`None` se va usato il predefinito) e gli argomenti posizionali in più,
+    /// oppure `None` dopo aver sollevato l'eccezione per un nome sconosciuto, ripetuto o per un parametro obbligatorio mancante.
+    /// Il numero di argomenti è già stato verificato da `is_function_arity_valid`.
+    fn collect_arguments(&mut self, kind: &str, callee: &str, params: &[(String, Option<Expression>)], args: &[Expression]) -> Option<(Vec<Option<Value>>, Vec<Value>)> {
+        let mut values: Vec<Option<Value>> = vec![None; params.len()];
+        let mut extra = Vec::new();
+        let mut position = 0;
+        for arg in args {
+            if let Expression::NamedArg { name, value } = arg {
+                let err_msg = match params.iter().position(|(param, _)| param == name) {
+                    Some(idx) if values[idx].is_none() => {
+                        values[idx] = Some(self.eval_expression(value));
+                        continue;
+                    }
+                    Some(_) => format!("Runtime Error: {} '{}' receives more than one value for parameter '{}'", kind, callee, name),
+                    None => format!("Runtime Error: {} '{}' has no parameter named '{}'", kind, callee, name),
+                };
+                self.exception = Some(Value::String(err_msg));
+                return None;
+            }
+            let val = self.eval_expression(arg);
+            match values.get_mut(position) {
+                Some(slot) => *slot = Some(val),
+                None => extra.push(val),
+            }
+            position += 1;
+        }
+        if self.exception.is_some() {
+            return None;
+        }
+        let missing = params.iter().zip(&values).find(|((_, default), val)| default.is_none() && val.is_none());
+        if let Some(((param, _), _)) = missing {
+            let err_msg = format!("Runtime Error: {} '{}' is missing an argument for parameter '{}'", kind, callee, param);
+            self.exception = Some(Value::String(err_msg));
+            return None;
+        }
+        Some((values, extra))
+    }
+}
+
+/// Nome della variabile che contiene il destinatario di una chiamata di metodo
+/// (il '$' non è ammesso negli identificatori, quindi non collide con le variabili dell'utente).
+const RECEIVER_VAR: &str = "$receiver";
+
+/// Numero massimo di bit del risultato di `pow` tra interi.
+const MAX_POW_BITS: u64 = 1 << 20;
+
+/// Potenza di un decimale con esponente intero (per quadrati successivi), `None` in caso di overflow.
+fn decimal_pow(mut base: Decimal, mut exponent: u32) -> Option<Decimal> {
+    let mut result = Decimal::ONE;
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result = result.checked_mul(base)?;
+        }
+        exponent /= 2;
+        if exponent > 0 {
+            base = base.checked_mul(base)?;
+        }
+    }
+    Some(result)
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::engine::ast::{Block, Expression, Statement};
+use crate::engine::value::Value;
+use super::iterators::{Iter, IterRef};
+use super::statements::{range_bound, Signals};
+use super::{Interpreter, VarEntry};
+
+/// Generatore sospeso: il corpo di una funzione che contiene `yield`, eseguito un valore alla volta.
+/// Mentre è sospeso i suoi scope sono fuori dallo stack dell'interprete.
+pub struct Generator {
+    pub scopes: Vec<HashMap<String, VarEntry>>,
+    // Punto di ripresa: i blocchi, i cicli e i try aperti, dal più esterno
+    pub frames: Vec<Frame>,
+}
+
+/// Costrutto aperto nel corpo di un generatore. Solo gli statement che contengono un `yield`
+/// diventano frame; gli altri sono eseguiti per intero dall'interprete.
+pub enum Frame {
+    // `next` è l'indice del prossimo statement; `scoped` indica se il blocco ha aperto uno scope
+    Block { stmts: Block, next: usize, scoped: bool },
+    // Ciclo che riceve il controllo alla fine di ogni giro del corpo
+    Loop { kind: LoopKind, body: Block, label: Option<String>, first: bool },
+    Try { stage: TryStage, catch_variable: Option<String>, catch_block: Option<Block>, finally_block: Option<Block> },
+}
+
+pub enum LoopKind {
+    While(Expression),
+    DoWhile(Expression),
+    For { iterator: String, source: IterRef },
+}
+
+pub enum TryStage {
+    Body,
+    Catch,
+    // Il blocco finally è in esecuzione, con i segnali sospesi in attesa della sua fine
+    Finally(Signals),
+    Finished,
+}
+
+impl Interpreter {
+    /// Crea il generatore per una chiamata: lo scope della funzione, già popolato con i parametri,
+    /// esce dallo stack e il corpo non viene eseguito finché non si chiede il primo valore.
+    pub fn create_generator(&mut self, scope_idx: usize, body: &Block) -> Value {
+        let mut frames = Vec::new();
+        self.push_block(&mut frames, body, false);
+        let scopes = self.scopes.split_off(scope_idx);
+        super::iterators::iter_value(Iter::Generator(Box::new(Generator { scopes, frames })))
+    }
+
+    /// Riprende il generatore fino al prossimo `yield`. `None` quando il corpo termina (anche con `return`)
+    /// o solleva un'eccezione, che resta attiva per il chiamante.
+    pub fn resume_generator(&mut self, generator: &mut Generator) -> Option<Value> {
+        let base = self.scopes.len();
+        self.scopes.append(&mut generator.scopes);
+        self.fn_scope_starts.push(base);
+        let item = self.run_frames(&mut generator.frames);
+        self.fn_scope_starts.pop();
+        generator.scopes = self.scopes.split_off(base);
+        if item.is_none() {
+            self.last_return = None;
+            generator.frames.clear();
+        }
+        item
+    }
+
+    fn push_block(&mut self, frames: &mut Vec<Frame>, stmts: &Block, scoped: bool) {
+        if scoped {
+            self.enter_scope();
+        }
+        self.hoist_functions(stmts);
+        frames.push(Frame::Block { stmts: Block::clone(stmts), next: 0, scoped });
+    }
+
+    /// Esegue i frame fino al prossimo `yield`. Un ritorno, un break, un continue o un'eccezione
+    /// chiudono i frame uno alla volta finché un ciclo o un try non li gestisce.
+    fn run_frames(&mut self, frames: &mut Vec<Frame>) -> Option<Value> {
+        while let Some(frame) = frames.last_mut() {
+            match frame {
+                Frame::Block { stmts, next, scoped } => {
+                    if self.signal_pending() || *next >= stmts.len() {
+                        if *scoped {
+                            self.exit_scope();
+                        }
+                        frames.pop();
+                        continue;
+                    }
+                    let stmts = Block::clone(stmts);
+                    let stmt = &stmts[*next];
+                    *next += 1;
+                    if !stmt.yields() {
+                        self.execute_statement(stmt);
+                        continue;
+                    }
+                    if let Some(item) = self.open_frame(frames, stmt) {
+                        return Some(item);
+                    }
+                }
+                Frame::Loop { kind, body, label, first } => {
+                    if (self.loop_break || self.loop_continue) && !self.settle_loop_signal(label.as_deref()) {
+                        frames.pop();
+                        continue;
+                    }
+                    if self.last_return.is_some() || self.exception.is_some() {
+                        frames.pop();
+                        continue;
+                    }
+                    let mut binding = None;
+                    let go_on = match kind {
+                        LoopKind::While(condition) => self.eval_expression(condition).is_truthy(),
+                        LoopKind::DoWhile(condition) => *first || self.eval_expression(condition).is_truthy(),
+                        LoopKind::For { iterator, source } => {
+                            let source = Rc::clone(source);
+                            binding = self.iter_next(&source).map(|item| (iterator.clone(), item));
+                            binding.is_some()
+                        }
+                    };
+                    *first = false;
+                    if !go_on || self.exception.is_some() {
+                        frames.pop();
+                        continue;
+                    }
+                    // Le funzioni del corpo sono registrate una volta sola, all'apertura del ciclo
+                    let stmts = Block::clone(body);
+                    self.enter_scope();
+                    frames.push(Frame::Block { stmts, next: 0, scoped: true });
+                    if let Some((iterator, item)) = binding {
+                        self.define_var(iterator, item, false);
+                    }
+                }
+                Frame::Try { stage, catch_variable, catch_block, finally_block } => match stage {
+                    TryStage::Body => {
+                        *stage = TryStage::Catch;
+                        if let (Some(_), Some(catch_stmts)) = (&self.exception, catch_block.clone()) {
+                            let exc = self.exception.take().unwrap_or(Value::Null);
+                            let catch_variable = catch_variable.clone();
+                            self.push_block(frames, &catch_stmts, true);
+                            if let Some(var_name) = catch_variable {
+                                self.define_var(var_name, exc, false);
+                            }
+                        }
+                    }
+                    TryStage::Catch => match finally_block.clone() {
+                        Some(finally_stmts) => {
+                            *stage = TryStage::Finally(self.suspend_signals());
+                            self.push_block(frames, &finally_stmts, true);
+                        }
+                        None => {
+                            frames.pop();
+                        }
+                    },
+                    TryStage::Finally(..) => {
+                        // Senza nuovi segnali nel finally riprende quello sospeso
+                        if let TryStage::Finally(saved) = std::mem::replace(stage, TryStage::Finished) {
+                            self.resume_signals(saved);
+                        }
+                        frames.pop();
+                    }
+                    TryStage::Finished => {
+                        frames.pop();
+                    }
+                },
+            }
+        }
+        None
+    }
+
+    /// Avvia uno statement che contiene un `yield`: un `yield` restituisce subito il suo valore,
+    /// gli altri costrutti aggiungono il proprio frame.
+    fn open_frame(&mut self, frames: &mut Vec<Frame>, stmt: &Statement) -> Option<Value> {
+        match stmt {
+            Statement::Yield { value, .. } => {
+                let item = self.eval_expression(value);
+                if self.exception.is_none() {
+                    return Some(item);
+                }
+            }
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+                let branch = if self.eval_expression(condition).is_truthy() { Some(then_branch) } else { else_branch.as_ref() };
+                if let (Some(branch), None) = (branch, &self.exception) {
+                    self.push_block(frames, branch, true);
+                }
+            }
+            Statement::WhileStatement { condition, body, label, .. } => {
+                self.hoist_functions(body);
+                let kind = LoopKind::While(condition.clone());
+                frames.push(Frame::Loop { kind, body: body.clone(), label: label.clone(), first: true });
+            }
+            Statement::DoWhileStatement { body, condition, label, .. } => {
+                self.hoist_functions(body);
+                let kind = LoopKind::DoWhile(condition.clone());
+                frames.push(Frame::Loop { kind, body: body.clone(), label: label.clone(), first: true });
+            }
+            Statement::ForStatement { iterator, start, end, body, label, .. } => {
+                let start_val = self.eval_expression(start);
+                let source = match end {
+                    Some(end) => {
+                        let end_val = self.eval_expression(end);
+                        let range = Iter::Range { next: range_bound(&start_val), end: range_bound(&end_val) };
+                        Some(Rc::new(std::cell::RefCell::new(range)))
+                    }
+                    None => self.make_iter(start_val),
+                };
+                if let (Some(source), None) = (source, &self.exception) {
+                    self.hoist_functions(body);
+                    let kind = LoopKind::For { iterator: iterator.clone(), source };
+                    frames.push(Frame::Loop { kind, body: body.clone(), label: label.clone(), first: true });
+                }
+            }
+            Statement::SwitchStatement { discriminant, cases, default_case, .. } => {
+                let disc_val = self.eval_expression(discriminant);
+                let mut branch = default_case.as_ref();
+                for (test_expr, body) in cases {
+                    if disc_val.equals(&self.eval_expression(test_expr)) {
+                        branch = Some(body);
+                        break;
+                    }
+                }
+                if let (Some(branch), None) = (branch, &self.exception) {
+                    self.push_block(frames, branch, true);
+                }
+            }
+            Statement::TryCatchStatement { try_block, catch_variable, catch_block, finally_block, .. } => {
+                frames.push(Frame::Try {
+                    stage: TryStage::Body,
+                    catch_variable: catch_variable.clone(),
+                    catch_block: catch_block.clone(),
+                    finally_block: finally_block.clone(),
+                });
+                self.push_block(frames, try_block, true);
+            }
+            other => self.execute_statement(other),
+        }
+        None
+    }
+}
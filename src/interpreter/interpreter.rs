@@ -1,6 +1,7 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use crate::engine::ast::{Program, Statement};
-use crate::engine::value::Value;
+use crate::engine::value::{Locale, Value};
 
 pub mod expressions;
 pub mod statements;
@@ -9,6 +10,12 @@ pub mod functions;
 pub mod fs;
 pub mod net;
 pub mod builtins;
+pub mod patterns;
+pub mod iterators;
+pub mod generators;
+pub mod conc;
+
+use self::conc::Scheduler;
 
 #[derive(Clone, Debug)]
 pub struct VarEntry {
@@ -19,11 +26,20 @@ pub struct VarEntry {
 pub struct Interpreter {
     pub scopes: Vec<HashMap<String, VarEntry>>,
     pub fn_scope_starts: Vec<usize>,
+    // Funzioni e record dichiarati (il nome di un record è il suo costruttore)
     pub functions: HashMap<String, Statement>,
     pub last_return: Option<Value>,
     pub loop_break: bool,
     pub loop_continue: bool,
+    // Etichetta del ciclo a cui è diretto il break o continue in corso (None: il ciclo più interno)
+    pub loop_label: Option<String>,
     pub exception: Option<Value>,
+    // Lingua in cui vengono stampati i valori e convertiti in stringa
+    pub locale: Locale,
+    // Vero se il programma importa `send` da nnet: solo allora send fuori da un canale è una richiesta POST
+    pub network_send: bool,
+    // Task e canali del programma (condiviso con i task avviati da `spawn`)
+    pub scheduler: Arc<Scheduler>,
 }
 
 impl Interpreter {
@@ -35,20 +51,30 @@ impl Interpreter {
             last_return: None,
             loop_break: false,
             loop_continue: false,
+            loop_label: None,
             exception: None,
+            locale: Locale::default(),
+            network_send: false,
+            scheduler: Scheduler::new(),
         }
     }
 
     /// Carica le definizioni globali come le funzioni
     pub fn load_program(&mut self, program: Program) {
+        self.hoist_functions(&program.statements);
         for stmt in &program.statements {
-            match stmt {
-                Statement::FunctionDecl { name, .. } => {
-                    self.functions.insert(name.clone(), stmt.clone());
-                }
-                _ => {
-                    self.execute_statement(stmt);
-                }
+            if !matches!(stmt, Statement::FunctionDecl { .. } | Statement::RecordDecl { .. }) {
+                self.execute_statement(stmt);
+            }
+        }
+    }
+
+    /// Registra le funzioni e i record dichiarati direttamente in un blocco prima di eseguirne le istruzioni,
+    /// così da poterle chiamare anche prima del punto in cui sono dichiarate.
+    pub fn hoist_functions(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            if let Statement::FunctionDecl { name, .. } | Statement::RecordDecl { name, .. } = stmt {
+                self.functions.insert(name.clone(), stmt.clone());
             }
         }
     }
@@ -63,16 +89,15 @@ impl Interpreter {
             return;
         }
 
-        if let Some(func_stmt) = self.functions.get("main").cloned() {
-            if let Statement::FunctionDecl { body, .. } = func_stmt {
-                for s in body {
-                    self.execute_statement(&s);
-                    if self.exception.is_some() {
-                        break;
-                    }
-                    if self.last_return.is_some() {
-                        break;
-                    }
+        if let Some(Statement::FunctionDecl { body, .. }) = self.functions.get("main").cloned() {
+            self.hoist_functions(&body);
+            for s in body.iter() {
+                self.execute_statement(s);
+                if self.exception.is_some() {
+                    break;
+                }
+                if self.last_return.is_some() {
+                    break;
                 }
             }
         }
@@ -113,10 +138,8 @@ impl Interpreter {
                 return true;
             }
         }
-        if start_idx > 0 {
-            if self.scopes[0].contains_key(name) {
-                return true;
-            }
+        if start_idx > 0 && self.scopes[0].contains_key(name) {
+            return true;
         }
         false
     }
@@ -139,10 +162,8 @@ impl Interpreter {
         if let Some(idx) = target_idx {
             return self.scopes[idx].get_mut(name);
         }
-        if start_idx > 0 {
-            if self.scopes[0].contains_key(name) {
-                return self.scopes[0].get_mut(name);
-            }
+        if start_idx > 0 && self.scopes[0].contains_key(name) {
+            return self.scopes[0].get_mut(name);
         }
         None
     }
@@ -152,46 +173,70 @@ impl Interpreter {
     }
 
     pub fn is_function_arity_valid(&self, func_name: &str, args_count: usize) -> bool {
-        match func_name {
-            "print" => true,
-            "input" => args_count <= 1,
-            "read" | "delete" | "sin" | "cos" | "sqrt" | "round" | "abs" | "log" | "sleep" | "fetch" | "len" => args_count == 1,
-            "write" | "min" | "max" | "pow" | "send" => args_count == 2,
-            "random" => args_count == 0,
-            "exit" => args_count <= 1,
-            _ => {
-                if let Some(Statement::FunctionDecl { params, .. }) = self.functions.get(func_name) {
-                    params.len() == args_count
-                } else {
-                    false
-                }
+        if let Some(valid) = builtins::is_builtin_arity_valid(func_name, args_count) {
+            return valid;
+        }
+        match self.functions.get(func_name) {
+            Some(Statement::FunctionDecl { params, rest, .. }) => {
+                let required = params.iter().filter(|(_, default)| default.is_none()).count();
+                args_count >= required && (rest.is_some() || args_count <= params.len())
             }
+            Some(Statement::RecordDecl { fields, .. }) => {
+                let required = fields.iter().filter(|(_, default)| default.is_none()).count();
+                (required..=fields.len()).contains(&args_count)
+            }
+            _ => false,
         }
     }
 
+    /// Assegna `new_val` all'elemento indicato da `path`. Gli array e le mappe intermedi sono
+    /// riferimenti condivisi: ogni livello viene modificato sul posto, rilasciando il prestito
+    /// prima di scendere al successivo (così `a[0][0] = 1` funziona anche se `a[0]` è `a`).
     pub fn mutate_value_at_path(val: &mut Value, path: &[Value], new_val: Value) -> Result<(), String> {
         if path.is_empty() {
             *val = new_val;
             return Ok(());
         }
-        match val {
+        let mut child = match val {
             Value::Array(arr) => {
                 let idx = match &path[0] {
                     Value::Integer(i) => *i as usize,
                     Value::Float(f) => *f as usize,
                     _ => return Err("L'indice dell'array deve essere un numero intero".to_string()),
                 };
+                let mut arr = arr.borrow_mut();
                 if idx >= arr.len() {
                     return Err("Indice dell'array fuori dai limiti".to_string());
                 }
-                Self::mutate_value_at_path(&mut arr[idx], &path[1..], new_val)
+                if path.len() == 1 {
+                    arr[idx] = new_val;
+                    return Ok(());
+                }
+                arr[idx].clone()
             }
             Value::Map(map) => {
                 let key = path[0].to_string();
-                let entry = map.entry(key).or_insert(Value::Null);
-                Self::mutate_value_at_path(entry, &path[1..], new_val)
+                let mut map = map.borrow_mut();
+                if path.len() == 1 {
+                    map.insert(key, new_val);
+                    return Ok(());
+                }
+                map.entry(key).or_insert(Value::Null).clone()
             }
-            _ => Err("Impossibile indicizzare questo tipo di dato".to_string()),
-        }
+            Value::Record(record) => {
+                let key = path[0].to_string();
+                let mut record = record.borrow_mut();
+                let Some(field) = record.fields.get_mut(&key) else {
+                    return Err(record.unknown_field(&key));
+                };
+                if path.len() == 1 {
+                    *field = new_val;
+                    return Ok(());
+                }
+                field.clone()
+            }
+            _ => return Err("Impossibile indicizzare questo tipo di dato".to_string()),
+        };
+        Self::mutate_value_at_path(&mut child, &path[1..], new_val)
     }
 }
\ No newline at end of file
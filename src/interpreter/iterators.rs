@@ -0,0 +1,235 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Lines};
+use std::rc::Rc;
+use crate::engine::ast::Expression;
+use crate::engine::value::Value;
+use super::generators::Generator;
+use super::Interpreter;
+
+/// Iteratore condiviso: gli adattatori e i cicli che lo ricevono ne consumano gli stessi valori.
+pub type IterRef = Rc<RefCell<Iter>>;
+
+/// Stato di un iteratore: produce un valore alla volta, solo quando viene richiesto.
+pub enum Iter {
+    // Elementi di un array letti al momento della richiesta (vede gli elementi aggiunti nel frattempo)
+    Array { items: Rc<RefCell<Vec<Value>>>, pos: usize },
+    // Interi da `next` a `end` escluso
+    Range { next: i64, end: i64 },
+    // Righe di un file di testo, lette una alla volta
+    Lines(Lines<BufReader<File>>),
+    Generator(Box<Generator>),
+    Take { inner: IterRef, remaining: usize },
+    Skip { inner: IterRef, pending: usize },
+    // Array con un valore per iteratore, fino all'esaurimento del più corto
+    Zip(Vec<IterRef>),
+    // Coppie [indice, valore]
+    Enumerate { inner: IterRef, index: i64 },
+    // Gli iteratori uno dopo l'altro, a partire da `current`
+    Chain { parts: Vec<IterRef>, current: usize },
+    // Estratto durante il calcolo del valore successivo: un generatore che chiede il proprio valore lo trova così
+    Running,
+    Done,
+}
+
+impl fmt::Debug for Iter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<iterator>")
+    }
+}
+
+/// Due iteratori sono uguali solo se sono lo stesso oggetto.
+impl PartialEq for Iter {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+pub fn iter_value(iter: Iter) -> Value {
+    Value::Iterator(Rc::new(RefCell::new(iter)))
+}
+
+impl Interpreter {
+    /// Iteratore sui valori di un iterabile: un iteratore è restituito così com'è, un array è letto
+    /// a ogni richiesta, insiemi, mappe (chiavi) e stringhe (caratteri) sono copiati al momento della chiamata.
+    pub fn make_iter(&mut self, value: Value) -> Option<IterRef> {
+        match value {
+            Value::Iterator(iter) => Some(iter),
+            Value::Array(items) => Some(Rc::new(RefCell::new(Iter::Array { items, pos: 0 }))),
+            other => match other.elements() {
+                Ok(items) => {
+                    let items = Rc::new(RefCell::new(items));
+                    Some(Rc::new(RefCell::new(Iter::Array { items, pos: 0 })))
+                }
+                Err(err_msg) => {
+                    if self.exception.is_none() {
+                        self.exception = Some(Value::String(err_msg));
+                    }
+                    None
+                }
+            },
+        }
+    }
+
+    /// Valore successivo di un iteratore, `None` se è esaurito o se il calcolo ha sollevato un'eccezione.
+    /// Un iteratore esaurito (o interrotto da un errore) resta tale.
+    pub fn iter_next(&mut self, iter: &IterRef) -> Option<Value> {
+        let mut state = std::mem::replace(&mut *iter.borrow_mut(), Iter::Running);
+        let item = match &mut state {
+            Iter::Running => {
+                if self.exception.is_none() {
+                    let err_msg = "Runtime Error: A generator cannot ask for its own next value".to_string();
+                    self.exception = Some(Value::String(err_msg));
+                }
+                return None;
+            }
+            Iter::Done => None,
+            Iter::Array { items, pos } => {
+                let item = items.borrow().get(*pos).cloned();
+                *pos += 1;
+                item
+            }
+            Iter::Range { next, end } => (*next < *end).then(|| {
+                *next += 1;
+                Value::Integer(*next - 1)
+            }),
+            Iter::Lines(lines) => match lines.next() {
+                Some(Ok(line)) => Some(Value::String(line)),
+                Some(Err(e)) => {
+                    self.exception = Some(Value::String(format!("FS Error: Could not read line. {}", e)));
+                    None
+                }
+                None => None,
+            },
+            Iter::Generator(generator) => self.resume_generator(generator),
+            Iter::Take { inner, remaining } => {
+                if *remaining == 0 {
+                    None
+                } else {
+                    *remaining -= 1;
+                    self.iter_next(inner)
+                }
+            }
+            Iter::Skip { inner, pending } => {
+                while *pending > 0 && self.iter_next(inner).is_some() {
+                    *pending -= 1;
+                }
+                *pending = 0;
+                self.iter_next(inner)
+            }
+            Iter::Zip(parts) => {
+                let mut items = Vec::with_capacity(parts.len());
+                for part in parts.iter() {
+                    match self.iter_next(part) {
+                        Some(item) => items.push(item),
+                        None => break,
+                    }
+                }
+                (items.len() == parts.len()).then(|| Value::array(items))
+            }
+            Iter::Enumerate { inner, index } => self.iter_next(inner).map(|item| {
+                *index += 1;
+                Value::array(vec![Value::Integer(*index - 1), item])
+            }),
+            Iter::Chain { parts, current } => {
+                let mut item = None;
+                while let Some(part) = parts.get(*current) {
+                    item = self.iter_next(part);
+                    if item.is_some() || self.exception.is_some() {
+                        break;
+                    }
+                    *current += 1;
+                }
+                item
+            }
+        };
+        if item.is_none() || self.exception.is_some() {
+            state = Iter::Done;
+        }
+        *iter.borrow_mut() = state;
+        if self.exception.is_some() {
+            return None;
+        }
+        item
+    }
+
+    /// Funzioni di sistema che creano, adattano e consumano gli iteratori.
+    /// Il numero di argomenti è già stato verificato da `is_function_arity_valid`.
+    pub fn handle_iterator_call(&mut self, name: &str, args: &[Expression]) -> Value {
+        let values: Vec<Value> = args.iter().map(|arg| self.eval_expression(arg)).collect();
+        if self.exception.is_some() {
+            return Value::Null;
+        }
+        let result = match name {
+            "range" => {
+                let bounds: Option<Vec<i64>> = values.iter().map(|v| match v {
+                    Value::Integer(i) => Some(*i),
+                    _ => None,
+                }).collect();
+                match bounds.as_deref() {
+                    Some([end]) => Ok(iter_value(Iter::Range { next: 0, end: *end })),
+                    Some([start, end]) => Ok(iter_value(Iter::Range { next: *start, end: *end })),
+                    _ => Err("TYPE ERROR: range expects integer bounds".to_string()),
+                }
+            }
+            "take" | "skip" => {
+                let count = match values[1] {
+                    Value::Integer(n) if n >= 0 => n as usize,
+                    ref other => {
+                        let err_msg = format!("TYPE ERROR: {} expects a non-negative integer count, got {}", name, other.display(&self.locale));
+                        self.exception = Some(Value::String(err_msg));
+                        return Value::Null;
+                    }
+                };
+                let Some(inner) = self.make_iter(values[0].clone()) else { return Value::Null };
+                Ok(iter_value(if name == "take" {
+                    Iter::Take { inner, remaining: count }
+                } else {
+                    Iter::Skip { inner, pending: count }
+                }))
+            }
+            "zip" | "chain" => {
+                let mut parts = Vec::with_capacity(values.len());
+                for value in values {
+                    let Some(part) = self.make_iter(value) else { return Value::Null };
+                    parts.push(part);
+                }
+                Ok(iter_value(if name == "zip" { Iter::Zip(parts) } else { Iter::Chain { parts, current: 0 } }))
+            }
+            "enumerate" => {
+                let Some(inner) = self.make_iter(values[0].clone()) else { return Value::Null };
+                Ok(iter_value(Iter::Enumerate { inner, index: 0 }))
+            }
+            // Un iteratore esaurito restituisce il valore predefinito, se indicato, altrimenti è un errore:
+            // così un `null` prodotto dal generatore non si confonde con la fine
+            "next" => match &values[0] {
+                Value::Iterator(iter) => match (self.iter_next(iter), values.get(1)) {
+                    (Some(item), _) => Ok(item),
+                    (None, _) if self.exception.is_some() => return Value::Null,
+                    (None, Some(default)) => Ok(default.clone()),
+                    (None, None) => Err("Runtime Error: next called on an exhausted iterator".to_string()),
+                },
+                other => Err(format!("TYPE ERROR: next expects an iterator, got {}", other.type_name())),
+            },
+            "collect" => {
+                let Some(iter) = self.make_iter(values[0].clone()) else { return Value::Null };
+                let mut items = Vec::new();
+                while let Some(item) = self.iter_next(&iter) {
+                    items.push(item);
+                }
+                Ok(Value::array(items))
+            }
+            _ => Ok(Value::Null),
+        };
+        match result {
+            Ok(value) => value,
+            Err(err_msg) => {
+                if self.exception.is_none() {
+                    self.exception = Some(Value::String(err_msg));
+                }
+                Value::Null
+            }
+        }
+    }
+}
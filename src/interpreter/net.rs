@@ -46,10 +46,12 @@ where F: Fn(&ureq::Agent) -> Result<ureq::Response, ureq::Error>
     }
 }
 
+#[allow(clippy::result_large_err)]
 pub fn get(url: &str) -> Result<Value, String> {
     perform_request("GET", url, |agent| agent.get(url).call())
 }
 
+#[allow(clippy::result_large_err)]
 pub fn post(url: &str, body: &str) -> Result<Value, String> {
     let json_val: serde_json::Value = serde_json::from_str(body)
         .unwrap_or(serde_json::Value::Null);
@@ -1,64 +1,96 @@
-use crate::engine::value::Value;
+use crate::engine::value::{SetIndex, Value};
 use super::Interpreter;
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+use rust_decimal::Decimal;
 
 impl Interpreter {
     pub fn eval_binary_op(&mut self, left: Value, operator: &str, right: Value) -> Value {
         if self.exception.is_some() {
             return Value::Null;
         }
-        match (left, right) {
-            (Value::Null, Value::Null) => match operator {
-                "==" => Value::Boolean(true),
-                "!=" => Value::Boolean(false),
-                _ => Value::Null,
-            },
-            (Value::Integer(a), Value::Integer(b)) => match operator {
-                "+" => {
-                    if let Some(res) = a.checked_add(b) {
-                        Value::Integer(res)
-                    } else {
-                        let err_msg = "MATH ERROR: Integer overflow during addition.".to_string();
-                        self.exception = Some(Value::String(err_msg));
-                        Value::Null
-                    }
-                }
-                "-" => {
-                    if let Some(res) = a.checked_sub(b) {
-                        Value::Integer(res)
-                    } else {
-                        let err_msg = "MATH ERROR: Integer underflow during subtraction.".to_string();
-                        self.exception = Some(Value::String(err_msg));
-                        Value::Null
-                    }
-                }
-                "*" => {
-                    if let Some(res) = a.checked_mul(b) {
-                        Value::Integer(res)
-                    } else {
-                        let err_msg = "MATH ERROR: Integer overflow during multiplication.".to_string();
+        if let (Value::Set(a), Value::Set(b)) = (&left, &right) {
+            if let Some(result) = eval_set_op(a, operator, b) {
+                return result.unwrap_or_else(|err_msg| {
+                    self.exception = Some(Value::String(err_msg));
+                    Value::Null
+                });
+            }
+        }
+        // Uguaglianza e ordinamento seguono le stesse regole per tutti i tipi
+        match operator {
+            "==" => return Value::Boolean(left.equals(&right)),
+            "!=" => return Value::Boolean(!left.equals(&right)),
+            "is" => return Value::Boolean(left.is_same(&right)),
+            "in" | "|" | "&" => {
+                let result = match operator {
+                    "in" => right.contains(&left).map(Value::Boolean),
+                    _ => Err(format!(
+                        "TYPE ERROR: Operator '{}' requires two sets, got {} and {}",
+                        operator, left.type_name(), right.type_name()
+                    )),
+                };
+                return result.unwrap_or_else(|err_msg| {
+                    self.exception = Some(Value::String(err_msg));
+                    Value::Null
+                });
+            }
+            ">" | "<" | ">=" | "<=" => {
+                return match left.compare(&right, self.locale.collate) {
+                    Ok(ordering) => Value::Boolean(match operator {
+                        ">" => ordering.is_gt(),
+                        "<" => ordering.is_lt(),
+                        ">=" => ordering.is_ge(),
+                        _ => ordering.is_le(),
+                    }),
+                    Err(err_msg) => {
                         self.exception = Some(Value::String(err_msg));
                         Value::Null
                     }
-                }
+                };
+            }
+            _ => {}
+        }
+        match (left, right) {
+            (Value::Integer(a), Value::Integer(b)) => match operator {
+                // In caso di overflow il calcolo prosegue con interi di precisione arbitraria
+                "+" => a.checked_add(b).map(Value::Integer).unwrap_or_else(|| Value::from_bigint(BigInt::from(a) + b)),
+                "-" => a.checked_sub(b).map(Value::Integer).unwrap_or_else(|| Value::from_bigint(BigInt::from(a) - b)),
+                "*" => a.checked_mul(b).map(Value::Integer).unwrap_or_else(|| Value::from_bigint(BigInt::from(a) * b)),
                 "/" => {
                     if b == 0 {
                         let err_msg = "MATH ERROR: Division by zero.".to_string();
                         self.exception = Some(Value::String(err_msg));
                         Value::Null
                     } else {
-                        Value::Integer(a / b)
+                        a.checked_div(b).map(Value::Integer).unwrap_or_else(|| Value::from_bigint(BigInt::from(a) / b))
                     }
                 }
-                ">" => Value::Boolean(a > b),
-                "<" => Value::Boolean(a < b),
-                ">=" => Value::Boolean(a >= b),
-                "<=" => Value::Boolean(a <= b),
-                "==" => Value::Boolean(a == b),
-                "!=" => Value::Boolean(a != b),
                 "&&" => Value::Boolean(a != 0 && b != 0),
                 "||" => Value::Boolean(a != 0 || b != 0),
                 _ => Value::Null,
             },
+            (Value::BigInt(a), Value::Integer(b)) => self.eval_bigint_op(a, operator, BigInt::from(b)),
+            (Value::Integer(a), Value::BigInt(b)) => self.eval_bigint_op(BigInt::from(a), operator, b),
+            (Value::BigInt(a), Value::BigInt(b)) => self.eval_bigint_op(a, operator, b),
+            // I decimali prevalgono sugli altri numeri, così che 0.1 resti esattamente 0,1
+            (l @ Value::Decimal(_), r) | (l, r @ Value::Decimal(_)) if l.as_decimal().is_some() && r.as_decimal().is_some() => {
+                match (l.as_decimal(), r.as_decimal()) {
+                    (Some(a), Some(b)) => self.eval_decimal_op(a, operator, b),
+                    _ => Value::Null,
+                }
+            }
+            (l @ Value::Decimal(_), r) | (l, r @ Value::Decimal(_)) if r.as_f64().is_some() && l.as_f64().is_some() => {
+                let err_msg = format!("MATH ERROR: Decimal overflow: {} is outside the decimal range.", if l.as_decimal().is_none() { l } else { r });
+                self.exception = Some(Value::String(err_msg));
+                Value::Null
+            }
+            (Value::BigInt(a), Value::Float(b)) => {
+                self.eval_binary_op(Value::Float(a.to_f64().unwrap_or(f64::INFINITY)), operator, Value::Float(b))
+            }
+            (Value::Float(a), Value::BigInt(b)) => {
+                self.eval_binary_op(Value::Float(a), operator, Value::Float(b.to_f64().unwrap_or(f64::INFINITY)))
+            }
             (Value::Float(a), Value::Float(b)) => match operator {
                 "+" => Value::Float(a + b),
                 "-" => Value::Float(a - b),
@@ -72,19 +104,11 @@ impl Interpreter {
                         Value::Float(a / b)
                     }
                 }
-                ">" => Value::Boolean(a > b),
-                "<" => Value::Boolean(a < b),
-                ">=" => Value::Boolean(a >= b),
-                "<=" => Value::Boolean(a <= b),
-                "==" => Value::Boolean(a == b),
-                "!=" => Value::Boolean(a != b),
                 _ => Value::Null,
             },
             (Value::Boolean(a), Value::Boolean(b)) => match operator {
                 "&&" => Value::Boolean(a && b),
                 "||" => Value::Boolean(a || b),
-                "==" => Value::Boolean(a == b),
-                "!=" => Value::Boolean(a != b),
                 _ => {
                     let err_msg = "TYPE ERROR: Invalid bool op".to_string();
                     self.exception = Some(Value::String(err_msg));
@@ -99,8 +123,6 @@ impl Interpreter {
             }
             (Value::String(a), Value::String(b)) => match operator {
                 "+" => Value::String(a + &b),
-                "==" => Value::Boolean(a == b),
-                "!=" => Value::Boolean(a != b),
                 _ => {
                     let err_msg = "TYPE ERROR: Invalid string op".to_string();
                     self.exception = Some(Value::String(err_msg));
@@ -108,9 +130,7 @@ impl Interpreter {
                 }
             },
             (Value::String(a), b) => match operator {
-                "+" => Value::String(format!("{}{}", a, b)),
-                "==" => Value::Boolean(false),
-                "!=" => Value::Boolean(true),
+                "+" => Value::String(a + &b.display(&self.locale)),
                 _ => {
                     let err_msg = format!("CRITICAL TYPE ERROR: Incompatible types for '{}': String and {:?}", operator, b);
                     self.exception = Some(Value::String(err_msg));
@@ -118,27 +138,95 @@ impl Interpreter {
                 }
             },
             (a, Value::String(b)) => match operator {
-                "+" => Value::String(format!("{}{}", a, b)),
-                "==" => Value::Boolean(false),
-                "!=" => Value::Boolean(true),
+                "+" => Value::String(a.display(&self.locale) + &b),
                 _ => {
                     let err_msg = format!("CRITICAL TYPE ERROR: Incompatible types for '{}': {:?} and String", operator, a);
                     self.exception = Some(Value::String(err_msg));
                     Value::Null
                 }
             },
-            (l, r) => match operator {
-                "==" => Value::Boolean(false),
-                "!=" => Value::Boolean(true),
-                _ => {
-                    let err_msg = format!(
-                        "CRITICAL TYPE ERROR: Incompatible types for '{}': {:?} and {:?}",
-                        operator, l, r
-                    );
+            (l, r) => {
+                let err_msg = format!(
+                    "CRITICAL TYPE ERROR: Incompatible types for '{}': {:?} and {:?}",
+                    operator, l, r
+                );
+                self.exception = Some(Value::String(err_msg));
+                Value::Null
+            }
+        }
+    }
+
+    /// Operazioni tra interi di precisione arbitraria; il risultato torna `Integer` se rientra in i64.
+    fn eval_bigint_op(&mut self, a: BigInt, operator: &str, b: BigInt) -> Value {
+        match operator {
+            "+" => Value::from_bigint(a + b),
+            "-" => Value::from_bigint(a - b),
+            "*" => Value::from_bigint(a * b),
+            "/" => {
+                if b.is_zero() {
+                    let err_msg = "MATH ERROR: Division by zero.".to_string();
                     self.exception = Some(Value::String(err_msg));
                     Value::Null
+                } else {
+                    Value::from_bigint(a / b)
                 }
-            },
+            }
+            "&&" => Value::Boolean(!a.is_zero() && !b.is_zero()),
+            "||" => Value::Boolean(!a.is_zero() || !b.is_zero()),
+            _ => Value::Null,
+        }
+    }
+
+    /// Operazioni tra decimali esatti (fino a 28 cifre significative, con arrotondamento
+    /// al pari dell'ultima cifra nelle divisioni non finite).
+    fn eval_decimal_op(&mut self, a: Decimal, operator: &str, b: Decimal) -> Value {
+        let result = match operator {
+            "+" => a.checked_add(b),
+            "-" => a.checked_sub(b),
+            "*" => a.checked_mul(b),
+            "/" => {
+                if b.is_zero() {
+                    let err_msg = "MATH ERROR: Division by zero.".to_string();
+                    self.exception = Some(Value::String(err_msg));
+                    return Value::Null;
+                }
+                a.checked_div(b)
+            }
+            _ => return Value::Null,
+        };
+        match result {
+            Some(d) => Value::Decimal(d),
+            None => {
+                let err_msg = "MATH ERROR: Decimal overflow.".to_string();
+                self.exception = Some(Value::String(err_msg));
+                Value::Null
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Algebra degli insiemi: unione (`|`), intersezione (`&`), differenza (`-`) e inclusione
+/// (`<=`, `<`, `>=`, `>`). `None` per gli operatori che seguono le regole generali (es. `==`).
+fn eval_set_op(a: &[Value], operator: &str, b: &[Value]) -> Option<Result<Value, String>> {
+    // `a >= b` equivale a `b <= a`: così basta cercare sempre nell'insieme di destra, indicizzato una volta
+    let (a, operator, b) = match operator {
+        ">=" => (b, "<=", a),
+        ">" => (b, "<", a),
+        _ => (a, operator, b),
+    };
+    if operator == "|" {
+        return Some(Value::set(a.iter().chain(b).cloned().collect()));
+    }
+    if !["&", "-", "<=", "<"].contains(&operator) {
+        return None;
+    }
+    let index = SetIndex::new(b);
+    let in_b = |x: &Value| index.contains(b, x);
+    let result = match operator {
+        "&" => Value::set(a.iter().filter(|x| in_b(x)).cloned().collect()),
+        "-" => Value::set(a.iter().filter(|x| !in_b(x)).cloned().collect()),
+        "<=" => Ok(Value::Boolean(a.iter().all(in_b))),
+        _ => Ok(Value::Boolean(a.len() < b.len() && a.iter().all(in_b))),
+    };
+    Some(result)
+}
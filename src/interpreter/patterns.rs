@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+use crate::engine::ast::{Expression, MatchArm, Pattern};
+use crate::engine::value::Value;
+use super::Interpreter;
+
+impl Interpreter {
+    /// Valuta un `match`: il valore del primo ramo il cui pattern corrisponde e la cui guardia è vera.
+    /// Le variabili legate dal pattern sono visibili solo nella guardia e nel valore del ramo.
+    pub fn eval_match(&mut self, subject: &Expression, arms: &[MatchArm], default: Option<&Expression>) -> Value {
+        let value = self.eval_expression(subject);
+        if self.exception.is_some() {
+            return Value::Null;
+        }
+        for arm in arms {
+            let mut bindings = Vec::new();
+            if !self.match_pattern(&arm.pattern, &value, &mut bindings) {
+                if self.exception.is_some() {
+                    return Value::Null;
+                }
+                continue;
+            }
+            self.enter_scope();
+            for (name, bound) in bindings {
+                self.define_var(name, bound, false);
+            }
+            let accepted = match &arm.guard {
+                Some(guard) => self.eval_expression(guard).is_truthy(),
+                None => true,
+            };
+            let result = accepted.then(|| self.eval_expression(&arm.value));
+            self.exit_scope();
+            if let Some(result) = result {
+                return result;
+            }
+            if self.exception.is_some() {
+                return Value::Null;
+            }
+        }
+        match default {
+            Some(expr) => self.eval_expression(expr),
+            None => {
+                let err_msg = format!("Runtime Error: No 'match' arm matches value {}", value.display(&self.locale));
+                self.exception = Some(Value::String(err_msg));
+                Value::Null
+            }
+        }
+    }
+
+    /// Verifica se `value` corrisponde al pattern, aggiungendo a `bindings` le variabili legate.
+    pub fn match_pattern(&mut self, pattern: &Pattern, value: &Value, bindings: &mut Vec<(String, Value)>) -> bool {
+        match pattern {
+            Pattern::Wildcard => true,
+            Pattern::Binding(name) => {
+                bindings.push((name.clone(), value.clone()));
+                true
+            }
+            Pattern::Literal(expr) => {
+                let expected = self.eval_expression(expr);
+                value.equals(&expected)
+            }
+            Pattern::Range { start, end } => {
+                let start = self.eval_expression(start);
+                let end = self.eval_expression(end);
+                // Valori non confrontabili con gli estremi (es. una stringa in un intervallo di numeri) non corrispondono
+                matches!(value.compare(&start, self.locale.collate), Ok(Ordering::Greater | Ordering::Equal))
+                    && matches!(value.compare(&end, self.locale.collate), Ok(Ordering::Less))
+            }
+            Pattern::Alternatives(options) => {
+                let mark = bindings.len();
+                for option in options {
+                    if self.match_pattern(option, value, bindings) {
+                        return true;
+                    }
+                    bindings.truncate(mark);
+                }
+                false
+            }
+            Pattern::Array { items, rest } => {
+                let Value::Array(arr) = value else { return false };
+                let elements = arr.borrow().clone();
+                let fits = match rest {
+                    Some(_) => elements.len() >= items.len(),
+                    None => elements.len() == items.len(),
+                };
+                if !fits || !self.match_all(items, &elements, bindings) {
+                    return false;
+                }
+                match rest {
+                    Some(rest) => self.match_pattern(rest, &Value::array(elements[items.len()..].to_vec()), bindings),
+                    None => true,
+                }
+            }
+            Pattern::Map(entries) => {
+                for (key, item) in entries {
+                    let field = match value {
+                        Value::Map(map) => map.borrow().get(key).cloned(),
+                        Value::Record(record) => record.borrow().fields.get(key).cloned(),
+                        _ => return false,
+                    };
+                    let Some(field) = field else { return false };
+                    if !self.match_pattern(item, &field, bindings) {
+                        return false;
+                    }
+                }
+                matches!(value, Value::Map(_) | Value::Record(_))
+            }
+            Pattern::Record { name, fields } => {
+                let Value::Record(record) = value else { return false };
+                let values: Vec<Value> = {
+                    let record = record.borrow();
+                    if &record.name != name || record.fields.len() != fields.len() {
+                        return false;
+                    }
+                    record.fields.values().cloned().collect()
+                };
+                self.match_all(fields, &values, bindings)
+            }
+            Pattern::Type { name, inner } => {
+                value.type_name() == name
+                    && match inner {
+                        Some(inner) => self.match_pattern(inner, value, bindings),
+                        None => true,
+                    }
+            }
+        }
+    }
+
+    /// Confronta i pattern con i valori nella stessa posizione.
+    fn match_all(&mut self, patterns: &[Pattern], values: &[Value], bindings: &mut Vec<(String, Value)>) -> bool {
+        patterns.iter().zip(values).all(|(pattern, value)| self.match_pattern(pattern, value, bindings))
+    }
+}
@@ -2,150 +2,178 @@ use crate::engine::ast::{Statement, Expression};
 use crate::engine::value::Value;
 use super::Interpreter;
 
+/// Ritorno, break, continue ed eccezione in corso, sospesi mentre è in esecuzione un blocco finally.
+pub struct Signals {
+    last_return: Option<Value>,
+    loop_break: bool,
+    loop_continue: bool,
+    loop_label: Option<String>,
+    exception: Option<Value>,
+}
+
+/// Estremo di un intervallo `a..b` di un ciclo `for`: i decimali sono troncati, gli altri valori valgono 0.
+pub fn range_bound(value: &Value) -> i64 {
+    match value {
+        Value::Integer(i) => *i,
+        Value::Float(f) => *f as i64,
+        _ => 0,
+    }
+}
+
 impl Interpreter {
     pub fn execute_statement(&mut self, stmt: &Statement) {
-        if self.last_return.is_some() || self.loop_break || self.loop_continue || self.exception.is_some() {
+        if self.signal_pending() {
             return;
         }
 
         match stmt {
-            Statement::VarDecl { name, value, is_mutable } => {
+            Statement::VarDecl { name, value, is_mutable, .. } => {
                 let val = self.eval_expression(value);
                 self.define_var(name.clone(), val, *is_mutable);
             }
-            Statement::Assignment { target, value } => {
+            Statement::PatternDecl { is_mutable, pattern, value, .. } => {
                 let val = self.eval_expression(value);
-                
-                let mut path = Vec::new();
-                let mut current = target;
-                while let Expression::Index { target: inner_target, index } = current {
-                    let idx_val = self.eval_expression(index);
-                    path.push(idx_val);
-                    current = inner_target;
+                if self.exception.is_some() {
+                    return;
                 }
-                
-                let var_name = match current {
-                    Expression::Variable(name) => name,
-                    _ => {
-                        let err_msg = "Target di assegnazione non valido.".to_string();
+                let mut bindings = Vec::new();
+                if !self.match_pattern(pattern, &val, &mut bindings) {
+                    if self.exception.is_none() {
+                        let err_msg = format!("Runtime Error: Cannot destructure {}: the value does not match the pattern", val.display(&self.locale));
                         self.exception = Some(Value::String(err_msg));
-                        return;
                     }
-                };
-                
-                path.reverse();
-                
-                if let Some(entry) = self.get_var_mut(var_name) {
-                    if !entry.is_mutable {
-                        let err_msg = format!("Impossibile assegnare a una costante '{}'.", var_name);
-                        self.exception = Some(Value::String(err_msg));
+                    return;
+                }
+                for (name, bound) in bindings {
+                    self.define_var(name, bound, *is_mutable);
+                }
+            }
+            Statement::Assignment { target, value, .. } => {
+                let val = self.eval_expression(value);
+                self.assign(target, val);
+            }
+            Statement::MultiAssignment { targets, values, .. } => {
+                let mut vals: Vec<Value> = values.iter().map(|v| self.eval_expression(v)).collect();
+                if self.exception.is_some() {
+                    return;
+                }
+                // Un solo valore a destra: un array con un elemento per destinazione
+                if vals.len() == 1 && targets.len() > 1 {
+                    let items = match &vals[0] {
+                        Value::Array(arr) if arr.borrow().len() == targets.len() => arr.borrow().clone(),
+                        other => {
+                            let err_msg = format!("Runtime Error: Cannot unpack {} into {} targets", other.display(&self.locale), targets.len());
+                            self.exception = Some(Value::String(err_msg));
+                            return;
+                        }
+                    };
+                    vals = items;
+                }
+                for (target, val) in targets.iter().zip(vals) {
+                    self.assign(target, val);
+                    if self.exception.is_some() {
                         return;
                     }
-                    if let Err(err) = Self::mutate_value_at_path(&mut entry.value, &path, val) {
-                        self.exception = Some(Value::String(err));
-                    }
-                } else {
-                    let err_msg = format!("Variabile '{}' non dichiarata prima dell'assegnazione.", var_name);
-                    self.exception = Some(Value::String(err_msg));
                 }
             }
-            Statement::IfStatement { condition, then_branch, else_branch } => {
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
                 let raw_cond = self.eval_expression(condition);
                 if raw_cond.is_truthy() {
                     self.enter_scope();
-                    for s in then_branch {
+                    self.hoist_functions(then_branch);
+                    for s in then_branch.iter() {
                         self.execute_statement(s);
-                        if self.last_return.is_some() || self.loop_break || self.loop_continue || self.exception.is_some() {
+                        if self.signal_pending() {
                             break;
                         }
                     }
                     self.exit_scope();
                 } else if let Some(else_stmts) = else_branch {
                     self.enter_scope();
-                    for s in else_stmts {
+                    self.hoist_functions(else_stmts);
+                    for s in else_stmts.iter() {
                         self.execute_statement(s);
-                        if self.last_return.is_some() || self.loop_break || self.loop_continue || self.exception.is_some() {
+                        if self.signal_pending() {
                             break;
                         }
                     }
                     self.exit_scope();
                 }
             }
-            Statement::WhileStatement { condition, body } => {
-                loop {
-                    let raw_cond = self.eval_expression(condition);
-                    if !raw_cond.is_truthy() {
+            Statement::WhileStatement { condition, body, label, .. } => {
+                self.hoist_functions(body);
+                while self.eval_expression(condition).is_truthy() {
+                    self.enter_scope();
+                    let go_on = self.run_loop_body(body, label.as_deref());
+                    self.exit_scope();
+                    if !go_on {
                         break;
                     }
-
+                }
+            }
+            Statement::DoWhileStatement { body, condition, label, .. } => {
+                self.hoist_functions(body);
+                loop {
                     self.enter_scope();
-                    for s in body {
-                        self.execute_statement(s);
-                        if self.last_return.is_some() || self.exception.is_some() {
-                            self.exit_scope();
-                            return;
-                        }
-                        if self.loop_break {
-                            self.exit_scope();
-                            self.loop_break = false;
-                            return;
-                        }
-                        if self.loop_continue {
-                            self.loop_continue = false;
-                            break;
-                        }
-                    }
+                    let go_on = self.run_loop_body(body, label.as_deref());
                     self.exit_scope();
+                    // La condizione è valutata fuori dallo scope del corpo
+                    if !go_on || !self.eval_expression(condition).is_truthy() {
+                        break;
+                    }
                 }
             }
-            Statement::ForStatement { iterator, start, end, body } => {
+            Statement::ForStatement { iterator, start, end, body, label, .. } => {
+                self.hoist_functions(body);
                 let start_val = self.eval_expression(start);
-                let end_val = self.eval_expression(end);
-
-                let start_int = match start_val {
-                    Value::Integer(i) => i,
-                    Value::Float(f) => f as i64,
-                    _ => 0,
-                };
-                let end_int = match end_val {
-                    Value::Integer(i) => i,
-                    Value::Float(f) => f as i64,
-                    _ => 0,
-                };
 
-                for i in start_int..end_int {
-                    self.enter_scope();
-                    self.define_var(iterator.clone(), Value::Integer(i), false);
-                    for s in body {
-                        self.execute_statement(s);
-                        if self.last_return.is_some() || self.exception.is_some() {
-                            self.exit_scope();
-                            return;
-                        }
-                        if self.loop_break {
-                            self.exit_scope();
-                            self.loop_break = false;
-                            return;
+                let Some(end) = end else {
+                    if self.exception.is_some() {
+                        return;
+                    }
+                    // Un iteratore produce i valori uno alla volta, anche all'infinito
+                    if let Value::Iterator(iter) = &start_val {
+                        while let Some(item) = self.iter_next(iter) {
+                            if !self.run_for_iteration(iterator, item, body, label.as_deref()) {
+                                break;
+                            }
                         }
-                        if self.loop_continue {
-                            self.loop_continue = false;
-                            break;
+                        return;
+                    }
+                    // Nessun intervallo: visita gli elementi della collezione
+                    match start_val.elements() {
+                        Ok(items) => {
+                            for item in items {
+                                if !self.run_for_iteration(iterator, item, body, label.as_deref()) {
+                                    break;
+                                }
+                            }
                         }
+                        Err(err_msg) => self.exception = Some(Value::String(err_msg)),
+                    }
+                    return;
+                };
+                let end_val = self.eval_expression(end);
+
+                for i in range_bound(&start_val)..range_bound(&end_val) {
+                    if !self.run_for_iteration(iterator, Value::Integer(i), body, label.as_deref()) {
+                        break;
                     }
-                    self.exit_scope();
                 }
             }
-            Statement::SwitchStatement { discriminant, cases, default_case } => {
+            Statement::SwitchStatement { discriminant, cases, default_case, .. } => {
                 let disc_val = self.eval_expression(discriminant);
                 let mut matched = false;
                 for (test_expr, body) in cases {
                     let test_val = self.eval_expression(test_expr);
-                    if disc_val == test_val {
+                    // Stesse regole di '==': confronto strutturale e numerico (es. 1 e 1.0)
+                    if disc_val.equals(&test_val) {
                         matched = true;
                         self.enter_scope();
-                        for s in body {
+                        self.hoist_functions(body);
+                        for s in body.iter() {
                             self.execute_statement(s);
-                            if self.last_return.is_some() || self.loop_break || self.loop_continue || self.exception.is_some() {
+                            if self.signal_pending() {
                                 break;
                             }
                         }
@@ -156,9 +184,10 @@ impl Interpreter {
                 if !matched {
                     if let Some(body) = default_case {
                         self.enter_scope();
-                        for s in body {
+                        self.hoist_functions(body);
+                        for s in body.iter() {
                             self.execute_statement(s);
-                            if self.last_return.is_some() || self.loop_break || self.loop_continue || self.exception.is_some() {
+                            if self.signal_pending() {
                                 break;
                             }
                         }
@@ -166,11 +195,12 @@ impl Interpreter {
                     }
                 }
             }
-            Statement::TryCatchStatement { try_block, catch_variable, catch_block, finally_block } => {
+            Statement::TryCatchStatement { try_block, catch_variable, catch_block, finally_block, .. } => {
                 self.enter_scope();
-                for s in try_block {
+                self.hoist_functions(try_block);
+                for s in try_block.iter() {
                     self.execute_statement(s);
-                    if self.exception.is_some() || self.last_return.is_some() || self.loop_break || self.loop_continue {
+                    if self.signal_pending() {
                         break;
                     }
                 }
@@ -183,9 +213,10 @@ impl Interpreter {
                         if let Some(ref var_name) = catch_variable {
                             self.define_var(var_name.clone(), exc, false);
                         }
-                        for s in catch_stmts {
+                        self.hoist_functions(catch_stmts);
+                        for s in catch_stmts.iter() {
                             self.execute_statement(s);
-                            if self.last_return.is_some() || self.loop_break || self.loop_continue || self.exception.is_some() {
+                            if self.signal_pending() {
                                 break;
                             }
                         }
@@ -194,46 +225,145 @@ impl Interpreter {
                 }
 
                 if let Some(finally_stmts) = finally_block {
-                    let saved_return = self.last_return.take();
-                    let saved_break = self.loop_break;
-                    let saved_continue = self.loop_continue;
-                    let saved_exception = self.exception.take();
-
-                    self.loop_break = false;
-                    self.loop_continue = false;
-
+                    let saved = self.suspend_signals();
                     self.enter_scope();
-                    for s in finally_stmts {
+                    self.hoist_functions(finally_stmts);
+                    for s in finally_stmts.iter() {
                         self.execute_statement(s);
                     }
                     self.exit_scope();
-
-                    if self.last_return.is_none() && !self.loop_break && !self.loop_continue && self.exception.is_none() {
-                        self.last_return = saved_return;
-                        self.loop_break = saved_break;
-                        self.loop_continue = saved_continue;
-                        self.exception = saved_exception;
-                    }
+                    self.resume_signals(saved);
                 }
             }
-            Statement::ThrowStatement { value } => {
+            Statement::ThrowStatement { value, .. } => {
                 self.exception = Some(self.eval_expression(value));
             }
-            Statement::ReturnStatement { value } => {
+            Statement::Yield { .. } => {
+                // Le funzioni che contengono `yield` sono eseguite come generatori (vedi `generators.rs`)
+                self.exception = Some(Value::String("Runtime Error: 'yield' outside a generator".to_string()));
+            }
+            Statement::ReturnStatement { value, .. } => {
                 self.last_return = Some(self.eval_expression(value));
             }
-            Statement::Break => {
+            Statement::Break { label, .. } => {
                 self.loop_break = true;
+                self.loop_label = label.clone();
             }
-            Statement::Continue => {
+            Statement::Continue { label, .. } => {
                 self.loop_continue = true;
+                self.loop_label = label.clone();
             }
-            Statement::FunctionDecl { name, .. } => {
+            Statement::FunctionDecl { name, .. } | Statement::RecordDecl { name, .. } => {
                 self.functions.insert(name.clone(), stmt.clone());
             }
-            Statement::Expr(expr) => {
+            Statement::Expr { expr, .. } => {
                 self.eval_expression(expr);
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Assegna `val` a una variabile o a un elemento indicato da un percorso di indici (`a[0]["k"]`).
+    fn assign(&mut self, target: &Expression, val: Value) {
+        let mut path = Vec::new();
+        let mut current = target;
+        while let Expression::Index { target: inner_target, index } = current {
+            let idx_val = self.eval_expression(index);
+            path.push(idx_val);
+            current = inner_target;
+        }
+        
+        let var_name = match current {
+            Expression::Variable(name) => name,
+            _ => {
+                let err_msg = "Target di assegnazione non valido.".to_string();
+                self.exception = Some(Value::String(err_msg));
+                return;
+            }
+        };
+        
+        path.reverse();
+        
+        if let Some(entry) = self.get_var_mut(var_name) {
+            if !entry.is_mutable {
+                let err_msg = format!("Impossibile assegnare a una costante '{}'.", var_name);
+                self.exception = Some(Value::String(err_msg));
+                return;
+            }
+            if let Err(err) = Self::mutate_value_at_path(&mut entry.value, &path, val) {
+                self.exception = Some(Value::String(err));
+            }
+        } else {
+            let err_msg = format!("Variabile '{}' non dichiarata prima dell'assegnazione.", var_name);
+            self.exception = Some(Value::String(err_msg));
+        }
+    }
+
+    /// Esegue un giro del ciclo `for` con l'iteratore legato a `value`.
+    /// Restituisce `false` se il ciclo deve terminare (break, return o eccezione).
+    fn run_for_iteration(&mut self, iterator: &str, value: Value, body: &[Statement], label: Option<&str>) -> bool {
+        self.enter_scope();
+        self.define_var(iterator.to_string(), value, false);
+        let go_on = self.run_loop_body(body, label);
+        self.exit_scope();
+        go_on
+    }
+
+    /// Esegue una volta il corpo di un ciclo nello scope corrente; le sue funzioni sono già
+    /// registrate all'inizio del ciclo, non a ogni giro.
+    /// Restituisce `false` se il ciclo deve terminare: un break o continue con l'etichetta
+    /// di un ciclo più esterno resta attivo e fa terminare anche questo.
+    fn run_loop_body(&mut self, body: &[Statement], label: Option<&str>) -> bool {
+        for s in body {
+            self.execute_statement(s);
+            if self.last_return.is_some() || self.exception.is_some() {
+                return false;
+            }
+            if self.loop_break || self.loop_continue {
+                return self.settle_loop_signal(label);
+            }
+        }
+        true
+    }
+
+    /// Vero se un ritorno, un break, un continue o un'eccezione interrompe il blocco in corso.
+    pub fn signal_pending(&self) -> bool {
+        self.last_return.is_some() || self.loop_break || self.loop_continue || self.exception.is_some()
+    }
+
+    /// Chiude il giro del ciclo con etichetta `label` che ha ricevuto un break o continue.
+    /// Restituisce `false` se il ciclo deve terminare: un'etichetta diversa appartiene a un ciclo
+    /// più esterno e resta attiva perché termini anche quello.
+    pub fn settle_loop_signal(&mut self, label: Option<&str>) -> bool {
+        if self.loop_label.is_some() && self.loop_label.as_deref() != label {
+            return false;
+        }
+        let go_on = !self.loop_break;
+        self.loop_label = None;
+        self.loop_break = false;
+        self.loop_continue = false;
+        go_on
+    }
+
+    /// Sospende i segnali in corso prima di un blocco finally.
+    pub fn suspend_signals(&mut self) -> Signals {
+        Signals {
+            last_return: self.last_return.take(),
+            loop_break: std::mem::take(&mut self.loop_break),
+            loop_continue: std::mem::take(&mut self.loop_continue),
+            loop_label: self.loop_label.take(),
+            exception: self.exception.take(),
+        }
+    }
+
+    /// Alla fine del blocco finally riprende i segnali sospesi, se il blocco non ne ha prodotti di nuovi.
+    pub fn resume_signals(&mut self, saved: Signals) {
+        if !self.signal_pending() {
+            self.last_return = saved.last_return;
+            self.loop_break = saved.loop_break;
+            self.loop_continue = saved.loop_continue;
+            self.loop_label = saved.loop_label;
+            self.exception = saved.exception;
+        }
+    }
+}
+
@@ -1,12 +1,17 @@
 use std::collections::HashMap;
+use std::fmt;
+use num_bigint::BigInt;
+use icu_properties::CodePointMapData;
+use icu_properties::props::{GeneralCategory, GeneralCategoryGroup};
 
 #[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Token {
     // Keyword generico contenente il nome inglese canonico (es. "let", "if", "sin")
     Keyword(String),
 
     // Identificatori e letterali
-    Identifier(String), StringLiteral(String), Number(f64),
+    Identifier(String), StringLiteral(String), Integer(i64), BigInt(BigInt), Float(f64),
 
     // Delimitatori (caricati da delimiters.json)
     Delimiter(String),
@@ -14,8 +19,38 @@ pub enum Token {
     // Operatori (caricati da operators.json)
     Operator(String),
 
+    // Stringhe con interpolazioni: TemplateStart, parti di testo (StringLiteral) ed espressioni
+    // racchiuse tra InterpolationStart e InterpolationEnd, TemplateEnd
+    TemplateStart, TemplateEnd, InterpolationStart, InterpolationEnd,
+
     EOF,
     Unknown(char),
+    // Errore lessicale con il relativo messaggio (es. sequenza di escape non valida)
+    Error(String),
+}
+
+/// Stampa il token come `{:?}` ma senza escape dei caratteri Unicode (es. i segni combinanti arabi),
+/// così che i messaggi di errore restino leggibili in ogni alfabeto.
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Keyword(s) => write!(f, "Keyword(\"{}\")", s),
+            Token::Identifier(s) => write!(f, "Identifier(\"{}\")", s),
+            Token::StringLiteral(s) => write!(f, "StringLiteral(\"{}\")", s),
+            Token::Integer(n) => write!(f, "Integer({})", n),
+            Token::BigInt(n) => write!(f, "BigInt({})", n),
+            Token::Float(n) => write!(f, "Float({:?})", n),
+            Token::Delimiter(s) => write!(f, "Delimiter(\"{}\")", s),
+            Token::Operator(s) => write!(f, "Operator(\"{}\")", s),
+            Token::TemplateStart => write!(f, "TemplateStart"),
+            Token::TemplateEnd => write!(f, "TemplateEnd"),
+            Token::InterpolationStart => write!(f, "InterpolationStart"),
+            Token::InterpolationEnd => write!(f, "InterpolationEnd"),
+            Token::EOF => write!(f, "EOF"),
+            Token::Unknown(c) => write!(f, "Unknown('{}')", c),
+            Token::Error(msg) => write!(f, "Error(\"{}\")", msg),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -47,10 +82,10 @@ impl Lexer {
 
         // Ordina per lunghezza decrescente per garantire la corrispondenza più lunga (longest-match)
         let mut delimiters: Vec<(String, String)> = delimiters_map.into_iter().collect();
-        delimiters.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        delimiters.sort_by_key(|d| std::cmp::Reverse(d.0.len()));
 
         let mut operators: Vec<(String, String)> = operators_map.into_iter().collect();
-        operators.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        operators.sort_by_key(|o| std::cmp::Reverse(o.0.len()));
 
         Self {
             input: input.chars().collect(),
@@ -78,86 +113,96 @@ impl Lexer {
     pub fn tokenize(&mut self, translation: &crate::engine::translate::TranslationEngine, filtered_engine: &crate::engine::filter::FilteredEngine) -> Vec<TokenWithSpan> {
         let mut tokens = Vec::new();
         while self.position < self.input.len() {
-            let char = self.input[self.position];
+            self.lex_token(&mut tokens, translation, filtered_engine);
+        }
 
-            // 1. Salta gli spazi bianchi
-            if char.is_whitespace() {
-                self.position += 1;
-                continue;
-            }
+        let end_pos = self.position;
+        let (line, col) = self.get_line_col(end_pos);
+        tokens.push(TokenWithSpan { token: Token::EOF, line, col });
+        tokens
+    }
 
-            // 2. Salta i commenti
-            if self.peek_str("//") {
-                self.skip_comment();
-                continue;
-            }
-            if self.peek_str("/*") {
-                self.skip_multiline_comment();
-                continue;
-            }
+    /// Riconosce il token che inizia nella posizione corrente e lo aggiunge a `tokens`
+    /// (le stringhe con interpolazioni ne aggiungono più d'uno, spazi e commenti nessuno).
+    fn lex_token(&mut self, tokens: &mut Vec<TokenWithSpan>, translation: &crate::engine::translate::TranslationEngine, filtered_engine: &crate::engine::filter::FilteredEngine) {
+        let char = self.input[self.position];
 
-            let start_pos = self.position;
-            let (line, col) = self.get_line_col(start_pos);
+        // 1. Salta gli spazi bianchi
+        if char.is_whitespace() {
+            self.position += 1;
+            return;
+        }
 
-            // 3. Cerca di fare match con gli operatori (dal più lungo)
-            let mut matched_op = None;
-            for (op_symbol, _) in &self.operators {
-                if self.peek_str(op_symbol) {
-                    matched_op = Some(op_symbol.clone());
-                    break;
-                }
-            }
-            if let Some(op) = matched_op {
-                self.position += op.chars().count();
-                tokens.push(TokenWithSpan { token: Token::Operator(op), line, col });
-                continue;
-            }
+        // 2. Salta i commenti
+        if self.peek_str("//") {
+            self.skip_comment();
+            return;
+        }
+        if self.peek_str("/*") {
+            self.skip_multiline_comment();
+            return;
+        }
 
-            // 4. Cerca di fare match con i delimitatori (dal più lungo)
-            let mut matched_delim = None;
-            for (delim_symbol, _) in &self.delimiters {
-                if self.peek_str(delim_symbol) {
-                    matched_delim = Some(delim_symbol.clone());
-                    break;
-                }
-            }
-            if let Some(delim) = matched_delim {
-                self.position += delim.chars().count();
-                tokens.push(TokenWithSpan { token: Token::Delimiter(delim), line, col });
-                continue;
-            }
+        let start_pos = self.position;
+        let (line, col) = self.get_line_col(start_pos);
 
-            // 5. Riconosce le stringhe letterali
-            if char == '"' {
-                let token = self.read_string();
-                tokens.push(TokenWithSpan { token, line, col });
-                continue;
+        // 3. Cerca di fare match con gli operatori (dal più lungo)
+        let mut matched_op = None;
+        for (op_symbol, _) in &self.operators {
+            if self.peek_str(op_symbol) {
+                matched_op = Some(op_symbol.clone());
+                break;
             }
+        }
+        if let Some(op) = matched_op {
+            self.position += op.chars().count();
+            tokens.push(TokenWithSpan { token: Token::Operator(op), line, col });
+            return;
+        }
 
-            // 6. Riconosce i numeri
-            if char.is_numeric() {
-                let token = self.read_number();
-                tokens.push(TokenWithSpan { token, line, col });
-                continue;
+        // 4. Cerca di fare match con i delimitatori (dal più lungo)
+        let mut matched_delim = None;
+        for (delim_symbol, _) in &self.delimiters {
+            if self.peek_str(delim_symbol) {
+                matched_delim = Some(delim_symbol.clone());
+                break;
             }
+        }
+        if let Some(delim) = matched_delim {
+            self.position += delim.chars().count();
+            tokens.push(TokenWithSpan { token: Token::Delimiter(delim), line, col });
+            return;
+        }
 
-            // 7. Riconosce identificatori/keyword
-            if char.is_alphabetic() || char == '_' {
-                let token = self.read_identifier(translation, filtered_engine);
-                tokens.push(TokenWithSpan { token, line, col });
-                continue;
+        // 5. Riconosce le stringhe letterali, anche raw (r"...")
+        if char == '"' || (char == 'r' && self.peek_next() == '"') {
+            self.read_string(tokens, translation, filtered_engine);
+            return;
+        }
+
+        // 6. Riconosce i numeri (con cifre decimali di qualsiasi alfabeto, es. arabo-indiche)
+        if decimal_digit_value(char).is_some() {
+            match self.read_number() {
+                Ok(token) => tokens.push(TokenWithSpan { token, line, col }),
+                Err(msg) => {
+                    // Un valore segnaposto permette di proseguire il parsing senza errori a cascata
+                    tokens.push(TokenWithSpan { token: Token::Integer(0), line, col });
+                    tokens.push(TokenWithSpan { token: Token::Error(msg), line, col });
+                }
             }
+            return;
+        }
 
-            // 8. Carattere sconosciuto
-            let token = Token::Unknown(char);
+        // 7. Riconosce identificatori/keyword
+        if char.is_alphabetic() || char == '_' {
+            let token = self.read_identifier(translation, filtered_engine);
             tokens.push(TokenWithSpan { token, line, col });
-            self.position += 1;
+            return;
         }
 
-        let end_pos = self.position;
-        let (line, col) = self.get_line_col(end_pos);
-        tokens.push(TokenWithSpan { token: Token::EOF, line, col });
-        tokens
+        // 8. Carattere sconosciuto
+        tokens.push(TokenWithSpan { token: Token::Unknown(char), line, col });
+        self.position += 1;
     }
 
     fn peek_str(&self, prefix: &str) -> bool {
@@ -175,7 +220,7 @@ impl Lexer {
 
     fn read_identifier(&mut self, translation: &crate::engine::translate::TranslationEngine, filtered_engine: &crate::engine::filter::FilteredEngine) -> Token {
         let start = self.position;
-        while self.position < self.input.len() && (self.input[self.position].is_alphanumeric() || self.input[self.position] == '_') {
+        while self.position < self.input.len() && is_identifier_continue(self.input[self.position]) {
             self.position += 1;
         }
         let text: String = self.input[start..self.position].iter().collect();
@@ -188,55 +233,272 @@ impl Lexer {
         }
     }
 
-    fn read_string(&mut self) -> Token {
-        self.position += 1; 
+    /// Legge una stringa letterale: normale ("..."), multilinea ("""...""") o raw (r"...", r"""..."""),
+    /// in cui escape e interpolazioni non vengono interpretati.
+    /// Senza interpolazioni `${...}` produce un solo StringLiteral, altrimenti la sequenza di token del template.
+    fn read_string(&mut self, tokens: &mut Vec<TokenWithSpan>, translation: &crate::engine::translate::TranslationEngine, filtered_engine: &crate::engine::filter::FilteredEngine) {
+        let (line, col) = self.get_line_col(self.position);
+        let raw = self.input[self.position] == 'r';
+        if raw {
+            self.position += 1;
+        }
+        let multiline = self.peek_str("\"\"\"");
+        self.position += if multiline { 3 } else { 1 };
+
+        // Nelle stringhe multilinea l'indentazione della riga di chiusura viene rimossa da ogni riga
+        let indent = if multiline { self.closing_indent() } else { None };
+        if multiline {
+            if self.peek_str("\r\n") {
+                self.position += 2;
+            } else if self.peek_str("\n") {
+                self.position += 1;
+            }
+            self.skip_indent(indent);
+        }
+
         let mut text = String::new();
+        let mut parts = Vec::new();
+        let mut errors = Vec::new();
+        let mut interpolated = false;
         let mut closed = false;
         while self.position < self.input.len() {
             let c = self.input[self.position];
-            if c == '\\' {
-                self.position += 1;
-                if self.position < self.input.len() {
-                    let escaped_char = self.input[self.position];
-                    match escaped_char {
-                        'n' => text.push('\n'), 't' => text.push('\t'), 'r' => text.push('\r'),
-                        '"' => text.push('"'), '\\' => text.push('\\'),
-                        _ => text.push(escaped_char),
-                    }
-                }
-            } else if c == '"' { 
+            if (multiline && self.peek_str("\"\"\"")) || (!multiline && c == '"') {
+                self.position += if multiline { 3 } else { 1 };
                 closed = true;
-                break; 
-            } 
-            else { text.push(c); }
+                break;
+            }
+            if c == '\\' && !raw {
+                let (escape_line, escape_col) = self.get_line_col(self.position);
+                if let Err(msg) = self.read_escape(&mut text) {
+                    errors.push(TokenWithSpan { token: Token::Error(msg), line: escape_line, col: escape_col });
+                }
+                continue;
+            }
+            if c == '$' && !raw && self.peek_next() == '{' {
+                interpolated = true;
+                if !text.is_empty() {
+                    parts.push(TokenWithSpan { token: Token::StringLiteral(std::mem::take(&mut text)), line, col });
+                }
+                let (start_line, start_col) = self.get_line_col(self.position);
+                parts.push(TokenWithSpan { token: Token::InterpolationStart, line: start_line, col: start_col });
+                self.position += 2;
+                if !self.read_interpolation(&mut parts, translation, filtered_engine) {
+                    break;
+                }
+                continue;
+            }
+            text.push(c);
             self.position += 1;
+            if c == '\n' {
+                self.skip_indent(indent);
+            }
         }
-        if closed {
-            self.position += 1;
-            Token::StringLiteral(text)
+
+        if !closed {
+            tokens.push(TokenWithSpan { token: Token::Unknown('"'), line, col });
+            return;
+        }
+        // La chiusura su una riga propria non aggiunge un a capo finale
+        if indent.is_some() && text.ends_with('\n') {
+            text.pop();
+            if text.ends_with('\r') {
+                text.pop();
+            }
+        }
+        if interpolated {
+            tokens.push(TokenWithSpan { token: Token::TemplateStart, line, col });
+            tokens.append(&mut parts);
+            if !text.is_empty() {
+                tokens.push(TokenWithSpan { token: Token::StringLiteral(text), line, col });
+            }
+            let (end_line, end_col) = self.get_line_col(self.position - 1);
+            tokens.push(TokenWithSpan { token: Token::TemplateEnd, line: end_line, col: end_col });
         } else {
-            Token::Unknown('"')
+            tokens.push(TokenWithSpan { token: Token::StringLiteral(text), line, col });
         }
+        tokens.append(&mut errors);
     }
 
-    fn read_number(&mut self) -> Token {
-        let start = self.position;
-        let mut has_dot = false;
-        while self.position < self.input.len() {
-            let c = self.input[self.position];
-            if c.is_numeric() { self.position += 1; } 
-            else if c == '.' && !has_dot {
-                if self.position + 1 < self.input.len() && self.input[self.position + 1] == '.' {
-                    break;
+    /// Interpreta la sequenza di escape nella posizione corrente e ne aggiunge il carattere al testo.
+    fn read_escape(&mut self, text: &mut String) -> Result<(), String> {
+        self.position += 1;
+        let Some(&escaped_char) = self.input.get(self.position) else {
+            return Ok(());
+        };
+        self.position += 1;
+        match escaped_char {
+            'n' => text.push('\n'), 't' => text.push('\t'), 'r' => text.push('\r'),
+            '0' => text.push('\0'),
+            '"' => text.push('"'), '\\' => text.push('\\'), '$' => text.push('$'),
+            'u' => {
+                // Forma \u{XXXX}: da 1 a 6 cifre esadecimali di un code point Unicode valido
+                if self.input.get(self.position) != Some(&'{') {
+                    return Err("Invalid Unicode escape: expected '\\u{' followed by 1 to 6 hex digits and '}'".to_string());
                 }
-                has_dot = true;
+                let start = self.position + 1;
+                let end = self.input[start..].iter().position(|c| *c == '}' || *c == '"' || *c == '\n').map(|i| start + i);
+                let Some(end) = end.filter(|end| self.input[*end] == '}') else {
+                    return Err("Invalid Unicode escape: missing closing '}'".to_string());
+                };
+                self.position = end + 1;
+                let digits: String = self.input[start..end].iter().collect();
+                if !(1..=6).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!("Invalid Unicode escape '\\u{{{}}}': expected 1 to 6 hex digits", digits));
+                }
+                match u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32) {
+                    Some(c) => text.push(c),
+                    None => return Err(format!("Invalid Unicode escape '\\u{{{}}}': not a valid code point", digits)),
+                }
+            }
+            _ => text.push(escaped_char),
+        }
+        Ok(())
+    }
+
+    /// Legge i token di un'interpolazione `${...}` fino alla '}' che la chiude.
+    /// Restituisce `false` se il sorgente termina prima della chiusura.
+    fn read_interpolation(&mut self, parts: &mut Vec<TokenWithSpan>, translation: &crate::engine::translate::TranslationEngine, filtered_engine: &crate::engine::filter::FilteredEngine) -> bool {
+        let mut depth = 0;
+        while self.position < self.input.len() {
+            if self.input[self.position] == '}' && depth == 0 {
+                let (line, col) = self.get_line_col(self.position);
+                parts.push(TokenWithSpan { token: Token::InterpolationEnd, line, col });
                 self.position += 1;
-            } 
-            else { break; }
+                return true;
+            }
+            let before = parts.len();
+            self.lex_token(parts, translation, filtered_engine);
+            match parts[before..].first().map(|t| &t.token) {
+                Some(Token::Delimiter(d)) if d == "{" => depth += 1,
+                Some(Token::Delimiter(d)) if d == "}" => depth -= 1,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Salta fino a `indent` spazi o tabulazioni all'inizio di una riga di una stringa multilinea.
+    fn skip_indent(&mut self, indent: Option<usize>) {
+        for _ in 0..indent.unwrap_or(0) {
+            match self.input.get(self.position) {
+                Some(' ') | Some('\t') => self.position += 1,
+                _ => break,
+            }
+        }
+    }
+
+    /// Indentazione della riga che chiude una stringa multilinea, se la chiusura è su una riga propria.
+    fn closing_indent(&self) -> Option<usize> {
+        let mut close = self.position;
+        while close + 3 <= self.input.len() {
+            if self.input[close..close + 3] == ['"', '"', '"'] && self.input[close - 1] != '\\' {
+                break;
+            }
+            close += 1;
+        }
+        if close + 3 > self.input.len() {
+            return None;
+        }
+        let line_start = self.input[..close].iter().rposition(|c| *c == '\n')? + 1;
+        let indentation = &self.input[line_start..close];
+        (line_start > self.position && indentation.iter().all(|c| *c == ' ' || *c == '\t')).then_some(indentation.len())
+    }
+
+    /// Legge un letterale numerico: intero (decimale, esadecimale `0x`, ottale `0o`, binario `0b`)
+    /// o decimale con esponente opzionale (`1.5e-3`). Le cifre possono essere separate da `_` (`1_000_000`).
+    fn read_number(&mut self) -> Result<Token, String> {
+        let start = self.position;
+        let radix = match (self.input[self.position], self.peek_next()) {
+            ('0', 'x' | 'X') => 16,
+            ('0', 'o' | 'O') => 8,
+            ('0', 'b' | 'B') => 2,
+            _ => 10,
+        };
+        let result = if radix == 10 {
+            self.read_decimal()
+        } else {
+            self.position += 2;
+            self.read_digits(radix).map(|digits| integer_token(&digits, radix))
+        };
+
+        // Lettere o cifre attaccate al numero lo rendono non valido (es. "12abc", "0x1G", "1e")
+        let mut result = result;
+        if let Some(&c) = self.input.get(self.position).filter(|c| is_identifier_continue(**c)) {
+            if result.is_ok() {
+                result = Err(format!("unexpected '{}'", c));
+            }
+            while self.position < self.input.len() && is_identifier_continue(self.input[self.position]) {
+                self.position += 1;
+            }
+        }
+        result.map_err(|reason| {
+            let text: String = self.input[start..self.position].iter().collect();
+            format!("Invalid number '{}': {}", text, reason)
+        })
+    }
+
+    /// Legge un numero in base 10, intero o decimale.
+    fn read_decimal(&mut self) -> Result<Token, String> {
+        let mut text = self.read_digits(10)?;
+        let mut is_float = false;
+        let is_digit_at = |lexer: &Self, pos: usize| lexer.input.get(pos).is_some_and(|c| decimal_digit_value(*c).is_some());
+
+        // La parte decimale richiede una cifra dopo il punto (così "1..5" resta un intervallo)
+        if self.input.get(self.position) == Some(&'.') && is_digit_at(self, self.position + 1) {
+            self.position += 1;
+            text.push('.');
+            text.push_str(&self.read_digits(10)?);
+            is_float = true;
+        }
+        if matches!(self.input.get(self.position), Some('e' | 'E')) {
+            let sign = self.input.get(self.position + 1).copied().filter(|c| *c == '+' || *c == '-');
+            let digits_pos = self.position + 1 + usize::from(sign.is_some());
+            if is_digit_at(self, digits_pos) {
+                self.position = digits_pos;
+                text.push('e');
+                text.extend(sign);
+                text.push_str(&self.read_digits(10)?);
+                is_float = true;
+            }
+        }
+
+        if is_float {
+            match text.parse::<f64>() {
+                Ok(value) if value.is_finite() => Ok(Token::Float(value)),
+                _ => Err("out of range for a 64-bit float".to_string()),
+            }
+        } else {
+            Ok(integer_token(&text, 10))
+        }
+    }
+
+    /// Legge una sequenza di cifre nella base indicata, eventualmente separate da `_`.
+    /// Le cifre decimali di qualsiasi alfabeto vengono convertite in ASCII.
+    fn read_digits(&mut self, radix: u32) -> Result<String, String> {
+        let mut digits = String::new();
+        let mut previous_underscore = false;
+        let mut misplaced_underscore = false;
+        while let Some(&c) = self.input.get(self.position) {
+            let digit = if radix == 10 { decimal_digit_value(c) } else { c.to_digit(radix) };
+            if let Some(digit) = digit {
+                digits.push(char::from_digit(digit, radix).unwrap_or('0'));
+                previous_underscore = false;
+            } else if c == '_' {
+                misplaced_underscore |= digits.is_empty() || previous_underscore;
+                previous_underscore = true;
+            } else {
+                break;
+            }
+            self.position += 1;
+        }
+        if digits.is_empty() {
+            Err("missing digits".to_string())
+        } else if misplaced_underscore || previous_underscore {
+            Err("'_' must be placed between digits".to_string())
+        } else {
+            Ok(digits)
         }
-        let text: String = self.input[start..self.position].iter().collect();
-        let value = text.parse::<f64>().unwrap_or(0.0);
-        Token::Number(value)
     }
 
     fn skip_comment(&mut self) {
@@ -255,4 +517,44 @@ impl Lexer {
         if self.position + 1 >= self.input.len() { return '\0'; }
         self.input[self.position + 1]
     }
-}
\ No newline at end of file
+}
+
+/// Token di un intero già validato: `Integer` se rientra in i64, altrimenti `BigInt`.
+fn integer_token(digits: &str, radix: u32) -> Token {
+    match i64::from_str_radix(digits, radix) {
+        Ok(value) => Token::Integer(value),
+        Err(_) => BigInt::parse_bytes(digits.as_bytes(), radix).map(Token::BigInt).unwrap_or(Token::Integer(0)),
+    }
+}
+
+/// Caratteri ammessi dopo il primo in un identificatore: lettere, cifre e `_` di qualsiasi alfabeto,
+/// più i segni combinanti (es. harakat arabe) ed i caratteri di unione ZWNJ/ZWJ.
+fn is_identifier_continue(c: char) -> bool {
+    c.is_alphanumeric()
+        || c == '_'
+        || c == '\u{200C}'
+        || c == '\u{200D}'
+        || GeneralCategoryGroup::Mark.contains(CodePointMapData::<GeneralCategory>::new().get(c))
+}
+
+/// Valore di una cifra decimale di qualsiasi alfabeto (es. '٣' -> 3), `None` per gli altri caratteri.
+fn decimal_digit_value(c: char) -> Option<u32> {
+    if c.is_ascii() {
+        return c.to_digit(10);
+    }
+    let categories = CodePointMapData::<GeneralCategory>::new();
+    if categories.get(c) != GeneralCategory::DecimalNumber {
+        return None;
+    }
+    // Unicode codifica le cifre decimali in sequenze contigue da 0 a 9
+    let mut offset = 0;
+    let mut code = c as u32;
+    while let Some(previous) = code.checked_sub(1).and_then(char::from_u32) {
+        if categories.get(previous) != GeneralCategory::DecimalNumber {
+            break;
+        }
+        offset += 1;
+        code -= 1;
+    }
+    Some(offset % 10)
+}
@@ -23,9 +23,59 @@ fn main() {
             }
             let filename = &args[2];
 
+            let Some(options) = parse_options(&args[3..]) else {
+                return;
+            };
+
             let mut engine = Engine::new();
+            engine.machine_output = options.machine_output;
+            engine.collate = options.collate;
+            engine.load_language_packs(&options.lang_packs);
             engine.run_file(filename);
         },
+        "vocab" => {
+            let Some(target) = args.get(2) else {
+                welcome::show_error("Missing option. Usage: cargo run -- vocab <language> | --conflicts [--lang-pack <path>]");
+                return;
+            };
+            let Some(options) = parse_options(&args[3..]) else {
+                return;
+            };
+            let mut engine = Engine::new();
+            engine.load_language_packs(&options.lang_packs);
+            // La lingua può essere indicata con il nome canonico o con una sua traduzione (es. "italiano")
+            let languages: Vec<String> = if target == "--conflicts" {
+                engine.translation_engine.languages().to_vec()
+            } else {
+                let language = engine.translation_engine.lookup_import(target, "translate", &engine.import_manager);
+                vec![language.unwrap_or(target).to_string()]
+            };
+            // I language pack vanno validati prima di mostrarne il vocabolario
+            for language in &languages {
+                if let Err(errors) = engine.translation_engine.activate_pack(language) {
+                    for error in errors {
+                        welcome::show_error(&error);
+                    }
+                    if target != "--conflicts" {
+                        return;
+                    }
+                }
+            }
+            for warning in engine.translation_engine.take_pack_warnings() {
+                welcome::show_warning(&warning);
+            }
+            let translation = &engine.translation_engine;
+            if target == "--conflicts" {
+                welcome::show_conflicts(&translation.find_conflicts());
+                return;
+            }
+            let language = languages[0].as_str();
+            if translation.is_language(language) {
+                welcome::show_vocabulary(language, &translation.vocabulary(language));
+            } else {
+                welcome::show_error(&format!("Unknown language: '{}'", target));
+            }
+        },
         "version" => {
             welcome::show_version();
         },
@@ -34,4 +84,37 @@ fn main() {
             welcome::show_usage();
         }
     }
-}
\ No newline at end of file
+}
+
+/// Opzioni della riga di comando successive al file o alla lingua.
+#[derive(Default)]
+struct Options {
+    lang_packs: Vec<String>,
+    machine_output: bool,
+    collate: bool,
+}
+
+/// Raccoglie le opzioni `--lang-pack <path>`, `--machine-output` e `--collate`.
+/// Restituisce `None` dopo aver mostrato l'errore se un'opzione non è valida.
+fn parse_options(args: &[String]) -> Option<Options> {
+    let mut options = Options::default();
+    let mut iter = args.iter();
+    while let Some(option) = iter.next() {
+        match option.as_str() {
+            "--lang-pack" => match iter.next() {
+                Some(path) => options.lang_packs.push(path.clone()),
+                None => {
+                    welcome::show_error("Missing path after '--lang-pack'.");
+                    return None;
+                }
+            },
+            "--machine-output" => options.machine_output = true,
+            "--collate" => options.collate = true,
+            _ => {
+                welcome::show_error(&format!("Unknown option: '{}'", option));
+                return None;
+            }
+        }
+    }
+    Some(options)
+}
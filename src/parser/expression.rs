@@ -1,14 +1,14 @@
 use crate::engine::ast::Expression;
 use crate::engine::lexer::Token;
-use super::Parser;
+use super::{ParseError, Parser};
 
 impl Parser {
-    pub fn parse_expression(&mut self) -> Result<Expression, String> {
+    pub fn parse_expression(&mut self) -> Result<Expression, ParseError> {
         self.parse_ternary()
     }
 
-    fn parse_ternary(&mut self) -> Result<Expression, String> {
-        let mut expr = self.parse_logical_or()?;
+    fn parse_ternary(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.parse_coalesce()?;
         if self.current_token() == &Token::Operator("?".to_string()) {
             self.advance();
             let true_expr = self.parse_expression()?;
@@ -23,7 +23,22 @@ impl Parser {
         Ok(expr)
     }
 
-    fn parse_logical_or(&mut self) -> Result<Expression, String> {
+    /// `a ?? b`: vale `b` solo se `a` è null.
+    fn parse_coalesce(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.parse_logical_or()?;
+        while self.current_token() == &Token::Operator("??".to_string()) {
+            self.advance();
+            let right = self.parse_logical_or()?;
+            left = Expression::BinaryOp {
+                left: Box::new(left),
+                operator: "??".to_string(),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_logical_or(&mut self) -> Result<Expression, ParseError> {
         let mut left = self.parse_logical_and()?;
         while self.current_token() == &Token::Operator("||".to_string()) {
             self.advance();
@@ -37,7 +52,7 @@ impl Parser {
         Ok(left)
     }
 
-    fn parse_logical_and(&mut self) -> Result<Expression, String> {
+    fn parse_logical_and(&mut self) -> Result<Expression, ParseError> {
         let mut left = self.parse_equality()?;
         while self.current_token() == &Token::Operator("&&".to_string()) {
             self.advance();
@@ -51,45 +66,76 @@ impl Parser {
         Ok(left)
     }
 
-    fn parse_equality(&mut self) -> Result<Expression, String> {
+    fn parse_equality(&mut self) -> Result<Expression, ParseError> {
         let mut left = self.parse_comparison()?;
-        while let Token::Operator(ref op) = self.current_token() {
-            if op == "==" || op == "!=" {
-                let operator = op.clone();
-                self.advance();
-                let right = self.parse_comparison()?;
-                left = Expression::BinaryOp {
-                    left: Box::new(left),
-                    operator,
-                    right: Box::new(right),
-                };
-            } else {
-                break;
-            }
+        loop {
+            // `is` (identità) ha la stessa precedenza di `==`
+            let operator = match self.current_token() {
+                Token::Operator(op) if op == "==" || op == "!=" => op.clone(),
+                Token::Keyword(kw) if kw == "is" => kw.clone(),
+                _ => break,
+            };
+            self.advance();
+            let right = self.parse_comparison()?;
+            left = Expression::BinaryOp {
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.parse_union()?;
+        loop {
+            // `x in collezione` (appartenenza) ha la stessa precedenza dei confronti
+            let operator = match self.current_token() {
+                Token::Operator(op) if op == ">" || op == ">=" || op == "<" || op == "<=" => op.clone(),
+                Token::Keyword(kw) if kw == "in" => kw.clone(),
+                _ => break,
+            };
+            self.advance();
+            let right = self.parse_union()?;
+            left = Expression::BinaryOp {
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            };
         }
         Ok(left)
     }
 
-    fn parse_comparison(&mut self) -> Result<Expression, String> {
+    /// Unione di insiemi (`|`), con precedenza minore dell'intersezione (`&`).
+    fn parse_union(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.parse_intersection()?;
+        while self.current_token() == &Token::Operator("|".to_string()) {
+            self.advance();
+            let right = self.parse_intersection()?;
+            left = Expression::BinaryOp {
+                left: Box::new(left),
+                operator: "|".to_string(),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_intersection(&mut self) -> Result<Expression, ParseError> {
         let mut left = self.parse_term()?;
-        while let Token::Operator(ref op) = self.current_token() {
-            if op == ">" || op == ">=" || op == "<" || op == "<=" {
-                let operator = op.clone();
-                self.advance();
-                let right = self.parse_term()?;
-                left = Expression::BinaryOp {
-                    left: Box::new(left),
-                    operator,
-                    right: Box::new(right),
-                };
-            } else {
-                break;
-            }
+        while self.current_token() == &Token::Operator("&".to_string()) {
+            self.advance();
+            let right = self.parse_term()?;
+            left = Expression::BinaryOp {
+                left: Box::new(left),
+                operator: "&".to_string(),
+                right: Box::new(right),
+            };
         }
         Ok(left)
     }
 
-    pub fn parse_term(&mut self) -> Result<Expression, String> {
+    pub fn parse_term(&mut self) -> Result<Expression, ParseError> {
         let mut left = self.parse_factor()?;
         while let Token::Operator(ref op) = self.current_token() {
             if op == "+" || op == "-" {
@@ -108,7 +154,7 @@ impl Parser {
         Ok(left)
     }
 
-    pub fn parse_factor(&mut self) -> Result<Expression, String> {
+    pub fn parse_factor(&mut self) -> Result<Expression, ParseError> {
         let mut left = self.parse_unary()?;
         while let Token::Operator(ref op) = self.current_token() {
             if op == "*" || op == "/" {
@@ -127,7 +173,7 @@ impl Parser {
         Ok(left)
     }
 
-    pub fn parse_unary(&mut self) -> Result<Expression, String> {
+    pub fn parse_unary(&mut self) -> Result<Expression, ParseError> {
         if let Token::Operator(ref op) = self.current_token() {
             if op == "-" || op == "!" {
                 let operator = op.clone();
@@ -142,7 +188,13 @@ impl Parser {
         self.parse_primary()
     }
 
-    pub fn parse_primary(&mut self) -> Result<Expression, String> {
+    /// Espressione primaria seguita da accessi `.campo` / `[indice]`, chiamate e chiamate di metodo.
+    pub fn parse_primary(&mut self) -> Result<Expression, ParseError> {
+        let expr = self.parse_atom()?;
+        self.parse_postfix(expr)
+    }
+
+    fn parse_atom(&mut self) -> Result<Expression, ParseError> {
         let token = self.current_token().clone();
         match token {
             Token::Keyword(ref kw) => {
@@ -155,9 +207,12 @@ impl Parser {
                 } else if kw == "null" {
                     self.advance();
                     Ok(Expression::LiteralNull)
+                } else if kw == "match" {
+                    self.parse_match()
                 } else {
-                    // Prova ad analizzarlo come funzione built-in o parola chiave di variabile (es. stampa, sen, ecc.)
-                    self.parse_identifier_or_keyword_expr(kw.clone())
+                    // Funzione built-in o parola chiave usata come nome (es. stampa, sen, ecc.)
+                    self.advance();
+                    Ok(Expression::Variable(kw.clone()))
                 }
             }
             Token::Delimiter(ref sym) if sym == "[" => {
@@ -175,13 +230,25 @@ impl Parser {
             }
             Token::Delimiter(ref sym) if sym == "{" => {
                 self.advance(); // consuma {
+                // `{a, b}` è un insieme: il primo elemento non è seguito da ':' (e `{}` resta una mappa vuota)
+                let is_map_key = matches!(self.current_token(), Token::StringLiteral(_) | Token::Identifier(_) | Token::Keyword(_))
+                    && self.peek_token() == &Token::Delimiter(":".to_string());
+                if !is_map_key && self.current_token() != &Token::Delimiter("}".to_string()) {
+                    let mut elements = vec![self.parse_expression()?];
+                    while self.current_token() == &Token::Delimiter(",".to_string()) {
+                        self.advance();
+                        elements.push(self.parse_expression()?);
+                    }
+                    self.consume(&Token::Delimiter("}".to_string()), "Expected '}' at end of set literal")?;
+                    return Ok(Expression::Set(elements));
+                }
                 let mut pairs = Vec::new();
                 if self.current_token() != &Token::Delimiter("}".to_string()) {
                     loop {
                         let key = match self.current_token() {
                             Token::StringLiteral(s) | Token::Identifier(s) => s.clone(),
                             Token::Keyword(k) => k.clone(),
-                            _ => return Err("Expected string or identifier as key in map literal".to_string()),
+                            _ => return Err(self.error("Expected string or identifier as key in map literal")),
                         };
                         self.advance();
                         self.consume(&Token::Delimiter(":".to_string()), "Expected ':' after map key")?;
@@ -201,12 +268,22 @@ impl Parser {
                 self.advance();
                 Ok(Expression::LiteralStr(s))
             }
-            Token::Number(n) => {
+            Token::TemplateStart => self.parse_template(),
+            Token::Integer(n) => {
+                self.advance();
+                Ok(Expression::LiteralInt(n))
+            }
+            Token::BigInt(n) => {
                 self.advance();
-                Ok(Expression::LiteralNum(n))
+                Ok(Expression::LiteralBigInt(n))
+            }
+            Token::Float(n) => {
+                self.advance();
+                Ok(Expression::LiteralFloat(n))
             }
             Token::Identifier(s) => {
-                self.parse_identifier_or_keyword_expr(s)
+                self.advance();
+                Ok(Expression::Variable(s))
             }
             Token::Delimiter(ref sym) if sym == "(" => {
                 self.advance();
@@ -214,29 +291,81 @@ impl Parser {
                 self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after parenthesized expression")?;
                 Ok(expr)
             }
-            _ => Err(format!(
-                "Syntax Error: Unexpected token {:?} at start of expression",
+            _ => Err(self.error(&format!(
+                "Unexpected token {} at start of expression",
                 self.current_token()
-            )),
+            ))),
         }
     }
 
-    fn parse_identifier_or_keyword_expr(&mut self, name_str: String) -> Result<Expression, String> {
-        let mut expr = Expression::Variable(name_str);
-        self.advance();
+    /// Analizza una stringa con interpolazioni: parti di testo ed espressioni `${...}` fino alla fine del template.
+    fn parse_template(&mut self) -> Result<Expression, ParseError> {
+        self.advance(); // consuma TemplateStart
+        let mut parts = Vec::new();
+        loop {
+            match self.current_token().clone() {
+                Token::StringLiteral(s) => {
+                    self.advance();
+                    parts.push(Expression::LiteralStr(s));
+                }
+                Token::InterpolationStart => {
+                    self.advance();
+                    if self.current_token() == &Token::InterpolationEnd {
+                        return Err(self.error("Empty string interpolation '${}'"));
+                    }
+                    parts.push(self.parse_expression()?);
+                    self.consume(&Token::InterpolationEnd, "Expected '}' at end of string interpolation")?;
+                }
+                Token::TemplateEnd => {
+                    self.advance();
+                    return Ok(Expression::Template(parts));
+                }
+                other => return Err(self.error(&format!("Unexpected token {} in string interpolation", other))),
+            }
+        }
+    }
 
+    fn parse_postfix(&mut self, mut expr: Expression) -> Result<Expression, ParseError> {
         loop {
             if self.current_token() == &Token::Delimiter(".".to_string()) {
                 self.advance();
                 let member = match self.current_token() {
                     Token::Identifier(s) => s.clone(),
                     Token::Keyword(s) => s.clone(),
-                    _ => return Err("Expected identifier after '.'".to_string()),
+                    _ => return Err(self.error("Expected identifier after '.'")),
                 };
                 self.advance();
-                expr = Expression::Index {
+                if self.current_token() == &Token::Delimiter("(".to_string()) {
+                    self.advance();
+                    let args = self.parse_arguments()?;
+                    expr = Expression::MethodCall { receiver: Box::new(expr), method: member, args };
+                } else {
+                    expr = Expression::Index {
+                        target: Box::new(expr),
+                        index: Box::new(Expression::LiteralStr(member)),
+                    };
+                }
+            } else if self.current_token() == &Token::Operator("?.".to_string()) {
+                self.advance();
+                let index = match self.current_token().clone() {
+                    Token::Identifier(s) | Token::Keyword(s) => {
+                        self.advance();
+                        if self.current_token() == &Token::Delimiter("(".to_string()) {
+                            return Err(self.error(&format!("Method '{}' cannot be called with '?.'", s)));
+                        }
+                        Expression::LiteralStr(s)
+                    }
+                    Token::Delimiter(ref sym) if sym == "[" => {
+                        self.advance();
+                        let index = self.parse_expression()?;
+                        self.consume(&Token::Delimiter("]".to_string()), "Expected ']' after index")?;
+                        index
+                    }
+                    _ => return Err(self.error("Expected identifier or '[' after '?.'")),
+                };
+                expr = Expression::OptionalIndex {
                     target: Box::new(expr),
-                    index: Box::new(Expression::LiteralStr(member)),
+                    index: Box::new(index),
                 };
             } else if self.current_token() == &Token::Delimiter("[".to_string()) {
                 self.advance();
@@ -248,15 +377,7 @@ impl Parser {
                 };
             } else if self.current_token() == &Token::Delimiter("(".to_string()) {
                 self.advance();
-                let mut args = Vec::new();
-                if self.current_token() != &Token::Delimiter(")".to_string()) {
-                    args.push(self.parse_expression()?);
-                    while self.current_token() == &Token::Delimiter(",".to_string()) {
-                        self.advance();
-                        args.push(self.parse_expression()?);
-                    }
-                }
-                self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after arguments")?;
+                let args = self.parse_arguments()?;
                 expr = Expression::FunctionCall {
                     target: Box::new(expr),
                     args,
@@ -267,4 +388,42 @@ impl Parser {
         }
         Ok(expr)
     }
+
+    /// Argomenti di una chiamata dopo la '(' fino alla ')' di chiusura.
+    /// Gli argomenti con nome (`parametro = valore`) seguono quelli posizionali.
+    fn parse_arguments(&mut self) -> Result<Vec<Expression>, ParseError> {
+        let mut args = Vec::new();
+        if self.current_token() != &Token::Delimiter(")".to_string()) {
+            loop {
+                args.push(self.parse_argument(&args)?);
+                if self.current_token() == &Token::Delimiter(",".to_string()) {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+        }
+        self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after arguments")?;
+        Ok(args)
+    }
+
+    fn parse_argument(&mut self, previous: &[Expression]) -> Result<Expression, ParseError> {
+        let named = match self.current_token() {
+            Token::Identifier(name) if self.peek_token() == &Token::Operator("=".to_string()) => Some(name.clone()),
+            _ => None,
+        };
+        let Some(name) = named else {
+            if previous.iter().any(|arg| matches!(arg, Expression::NamedArg { .. })) {
+                return Err(self.error("Positional argument follows a named argument"));
+            }
+            return self.parse_expression();
+        };
+        if previous.iter().any(|arg| matches!(arg, Expression::NamedArg { name: other, .. } if *other == name)) {
+            return Err(self.error(&format!("Duplicate named argument '{}'", name)));
+        }
+        self.advance();
+        self.advance(); // consuma =
+        let value = self.parse_expression()?;
+        Ok(Expression::NamedArg { name, value: Box::new(value) })
+    }
 }
\ No newline at end of file
@@ -1,12 +1,60 @@
+use std::collections::HashMap;
+use std::fmt;
 use crate::engine::lexer::{Token, TokenWithSpan};
+use self::pattern::TYPE_PATTERNS;
 
 pub mod expression;
+pub mod pattern;
 pub mod statement;
 
+/// Cicli che sono keyword solo all'inizio di uno statement e seguiti da `{` (vedi `CONTEXTUAL_KEYWORDS`).
+const LOOP_KEYWORDS: &[&str] = &["loop", "do", "repeat"];
+
+/// Keyword che possono iniziare uno statement: punti di risincronizzazione dopo un errore.
+const SYNC_KEYWORDS: &[&str] = &[
+    "let", "const", "if", "while", "for", "switch", "case", "default", "try",
+    "throw", "break", "continue", "return", "function", "do", "repeat", "loop", "yield",
+];
+
+/// Errore rilevato dal parser, con la posizione nel sorgente usata per ordinare e deduplicare gli errori.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: &'static str,
+    pub line: usize,
+    pub col: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn syntax(line: usize, col: usize, message: String) -> Self {
+        Self { kind: "Syntax Error", line, col, message }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (Line {}, Col {}): {}", self.kind, self.line, self.col, self.message)
+    }
+}
+
 pub struct Parser {
     pub tokens: Vec<TokenWithSpan>,
     pub position: usize,
     pub loop_depth: usize,
+    // Etichette dei cicli che racchiudono la posizione corrente
+    loop_labels: Vec<String>,
+    // Vero durante l'analisi del corpo di una funzione, dove è ammesso `yield`
+    in_function: bool,
+    // Errori sintattici raccolti durante il parsing in modalità panic
+    pub errors: Vec<ParseError>,
+    // Associa l'indice di ogni '{' all'indice della '}' corrispondente
+    brace_pairs: HashMap<usize, usize>,
+    // Indici delle '}' che chiudono i blocchi aperti (None se la graffa non è bilanciata)
+    block_ends: Vec<Option<usize>>,
+    // Indici delle '{' dei blocchi spuri segnalati dai controlli preliminari
+    spurious_blocks: Vec<usize>,
+    // Grafie normalizzate delle keyword contestuali delle lingue importate (es. "intero" -> "integer")
+    contextual_keywords: HashMap<String, String>,
 }
 
 impl Parser {
@@ -15,22 +63,153 @@ impl Parser {
             tokens,
             position: 0,
             loop_depth: 0,
+            loop_labels: Vec::new(),
+            in_function: false,
+            errors: Vec::new(),
+            brace_pairs: HashMap::new(),
+            block_ends: Vec::new(),
+            spurious_blocks: Vec::new(),
+            contextual_keywords: HashMap::new(),
         }
     }
 
     /// Funzione di parsing principale.
-    pub fn parse(&mut self, translation_engine: &crate::engine::translate::TranslationEngine, import_manager: &crate::engine::import::ImportManager) -> Result<crate::engine::ast::Program, String> {
-        // 1. Controlli strutturali e di importazione preliminari
-        self.pre_check(translation_engine, import_manager)?;
+    /// Non si ferma al primo errore: restituisce tutti gli errori strutturali, di importazione e sintattici del file.
+    pub fn parse(&mut self, translation_engine: &crate::engine::translate::TranslationEngine, import_manager: &crate::engine::import::ImportManager) -> Result<crate::engine::ast::Program, Vec<String>> {
+        // 1. Keyword contestuali, poi controlli strutturali e di importazione preliminari
+        let contextual: Vec<&str> = TYPE_PATTERNS.iter().chain(LOOP_KEYWORDS).chain(&["until"]).copied().collect();
+        self.contextual_keywords = translation_engine.active_spellings(&contextual, import_manager);
+        self.resolve_loop_keywords();
+        let mut errors = self.pre_check(translation_engine, import_manager);
 
         // 2. Converte i token in statement dell'AST
         self.position = 0;
+        self.errors.clear();
         let mut statements = Vec::new();
         while self.position < self.tokens.len() && self.current_token() != &Token::EOF {
-            statements.push(self.parse_statement()?);
+            if let Some(stmt) = self.parse_statement_or_recover() {
+                statements.push(stmt);
+            }
+        }
+
+        // Gli errori dentro un blocco spurio sono conseguenze di quello già segnalato
+        let spurious_ranges: Vec<((usize, usize), (usize, usize))> = self
+            .spurious_blocks
+            .iter()
+            .filter_map(|open| self.brace_pairs.get(open).map(|close| (*open, *close)))
+            .map(|(open, close)| {
+                let (start, end) = (&self.tokens[open], &self.tokens[close]);
+                ((start.line, start.col), (end.line, end.col))
+            })
+            .collect();
+
+        // Un errore sintattico nella stessa posizione di uno già rilevato dai controlli preliminari è ridondante
+        for err in std::mem::take(&mut self.errors) {
+            let position = (err.line, err.col);
+            let is_cascade = spurious_ranges.iter().any(|(start, end)| *start < position && position <= *end);
+            if !is_cascade && !errors.iter().any(|e| (e.line, e.col) == position) {
+                errors.push(err);
+            }
+        }
+        // Ordina gli errori per posizione nel sorgente (l'ordinamento è stabile a parità di posizione)
+        errors.sort_by_key(|e| (e.line, e.col));
+        if errors.is_empty() {
+            Ok(crate::engine::ast::Program { statements })
+        } else {
+            Err(errors.iter().map(ParseError::to_string).collect())
+        }
+    }
+
+    /// Analizza uno statement; in caso di errore lo registra e risincronizza il parser (panic mode).
+    pub fn parse_statement_or_recover(&mut self) -> Option<crate::engine::ast::Statement> {
+        let start = self.position;
+        match self.parse_statement() {
+            Ok(stmt) => Some(stmt),
+            Err(err) => {
+                self.errors.push(err);
+                self.synchronize(start);
+                None
+            }
+        }
+    }
+
+    /// Scarta i token fino al prossimo punto sicuro: una keyword di inizio statement
+    /// oppure la '}' che chiude il blocco corrente (che non viene consumata).
+    pub fn synchronize(&mut self, start: usize) {
+        // Garantisce l'avanzamento se l'errore è sul primo token dello statement
+        if self.position == start {
+            self.advance();
+        }
+
+        let block_end = self.block_ends.last().copied();
+        let mut nesting = 0;
+        while self.current_token() != &Token::EOF {
+            match self.current_token() {
+                Token::Delimiter(sym) if sym == "{" => nesting += 1,
+                Token::Delimiter(sym) if sym == "}" => {
+                    match block_end {
+                        Some(Some(end)) if end == self.position => return,
+                        Some(None) if nesting == 0 => return,
+                        _ => {}
+                    }
+                    if nesting > 0 {
+                        nesting -= 1;
+                    }
+                }
+                Token::Keyword(kw) if nesting == 0 && SYNC_KEYWORDS.contains(&kw.as_str()) => return,
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    /// Trasforma in keyword i cicli scritti come identificatori dove non possono essere nomi:
+    /// `loop`, `do` e `repeat` all'inizio di uno statement e seguiti da `{`, `until` subito dopo
+    /// la `}` che chiude il corpo di un `repeat`. Altrove restano nomi liberi (es. `crea ciclo = 1`).
+    fn resolve_loop_keywords(&mut self) {
+        // Per ogni '{' aperta: vero se apre il corpo di un `repeat`
+        let mut open_braces: Vec<bool> = Vec::new();
+        let mut closed_repeat = false;
+        for i in 0..self.tokens.len() {
+            let after_repeat = std::mem::take(&mut closed_repeat);
+            let resolved = match &self.tokens[i].token {
+                Token::Identifier(name) => self.contextual_keyword(name, LOOP_KEYWORDS).filter(|_| {
+                    self.tokens.get(i + 1).is_some_and(|next| next.token == Token::Delimiter("{".to_string()))
+                        && self.starts_statement(i)
+                }).or_else(|| self.contextual_keyword(name, &["until"]).filter(|_| after_repeat)),
+                Token::Delimiter(d) if d == "{" => {
+                    let opens_repeat = i > 0 && self.tokens[i - 1].token == Token::Keyword("repeat".to_string());
+                    open_braces.push(opens_repeat);
+                    None
+                }
+                Token::Delimiter(d) if d == "}" => {
+                    closed_repeat = open_braces.pop().unwrap_or(false);
+                    None
+                }
+                _ => None,
+            };
+            if let Some(canonical) = resolved {
+                self.tokens[i].token = Token::Keyword(canonical);
+            }
         }
+    }
+
+    /// Vero se il token in posizione `index` inizia uno statement: primo del file o della riga,
+    /// dopo una graffa o un ';', oppure dopo l'etichetta di un ciclo (`esterno: ciclo { ... }`).
+    fn starts_statement(&self, index: usize) -> bool {
+        let Some(previous) = index.checked_sub(1).map(|i| &self.tokens[i]) else {
+            return true;
+        };
+        previous.line < self.tokens[index].line
+            || matches!(&previous.token, Token::Delimiter(d) if d == "{" || d == "}" || d == ";" || d == ":")
+    }
 
-        Ok(crate::engine::ast::Program { statements })
+    /// Forma canonica di un identificatore che, in questa posizione, va letto come keyword contestuale.
+    pub fn contextual_keyword(&self, name: &str, allowed: &[&str]) -> Option<String> {
+        self.contextual_keywords
+            .get(&crate::engine::translate::fold(name))
+            .filter(|canonical| allowed.contains(&canonical.as_str()))
+            .cloned()
     }
 
     /// Restituisce il token corrente.
@@ -42,6 +221,14 @@ impl Parser {
         }
     }
 
+    /// Restituisce il token successivo a quello corrente, senza avanzare.
+    pub fn peek_token(&self) -> &Token {
+        match self.tokens.get(self.position + 1) {
+            Some(t) => &t.token,
+            None => &Token::EOF,
+        }
+    }
+
     pub fn current_location(&self) -> (usize, usize) {
         if self.position >= self.tokens.len() {
             if let Some(last) = self.tokens.last() {
@@ -54,6 +241,12 @@ impl Parser {
         }
     }
 
+    /// Costruisce un messaggio di errore sintattico con la posizione del token corrente.
+    pub fn error(&self, msg: &str) -> ParseError {
+        let (line, col) = self.current_location();
+        ParseError::syntax(line, col, msg.to_string())
+    }
+
     /// Avanza l'indice del parser.
     pub fn advance(&mut self) -> &Token {
         if self.position < self.tokens.len() {
@@ -63,214 +256,234 @@ impl Parser {
     }
 
     /// Consuma un token specifico o solleva un errore sintattico.
-    pub fn consume(&mut self, expected: &Token, err_msg: &str) -> Result<(), String> {
+    pub fn consume(&mut self, expected: &Token, err_msg: &str) -> Result<(), ParseError> {
         if self.current_token() == expected {
             self.advance();
             Ok(())
         } else {
-            let (line, col) = self.current_location();
-            Err(format!(
-                "Syntax Error (Line {}, Col {}): {} (found {:?})",
-                line, col, err_msg, self.current_token()
-            ))
+            Err(self.error(&format!("{} (found {})", err_msg, self.current_token())))
         }
     }
 
+    /// Registra l'inizio di un blocco la cui '{' è appena stata consumata.
+    pub fn enter_block(&mut self) {
+        let end = self.brace_pairs.get(&(self.position - 1)).copied();
+        self.block_ends.push(end);
+    }
+
+    /// Chiude il blocco aperto più interno.
+    pub fn exit_block(&mut self) {
+        self.block_ends.pop();
+    }
+
     /// Controlli pre-parsing per bilanciamento parentesi e collisioni di nomi.
-    fn pre_check(&self, translation_engine: &crate::engine::translate::TranslationEngine, import_manager: &crate::engine::import::ImportManager) -> Result<(), String> {
+    /// Raccoglie tutti gli errori invece di fermarsi al primo.
+    fn pre_check(&mut self, translation_engine: &crate::engine::translate::TranslationEngine, import_manager: &crate::engine::import::ImportManager) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+
+        // Riporta gli errori lessicali e rimuove i relativi token, così che il parser non li veda
+        for token_ws in &self.tokens {
+            if let Token::Error(ref msg) = token_ws.token {
+                errors.push(ParseError::syntax(token_ws.line, token_ws.col, msg.clone()));
+            }
+        }
+        self.tokens.retain(|token_ws| !matches!(token_ws.token, Token::Error(_)));
+
         // Rileva stringhe letterali non chiuse a fine file
         for token_ws in &self.tokens {
             if let Token::Unknown('"') = token_ws.token {
-                return Err(format!(
-                    "Syntax Error (Line {}, Col {}): Unclosed string literal",
-                    token_ws.line, token_ws.col
+                errors.push(ParseError::syntax(
+                    token_ws.line, token_ws.col,
+                    "Unclosed string literal".to_string(),
                 ));
             }
         }
 
-        // Verifica che le funzioni di sistema usate siano effettivamente importate
-        for token_ws in &self.tokens {
+        // Verifica che le funzioni di sistema chiamate siano effettivamente importate.
+        // Senza import la parola è un nome libero: viene segnalata solo se chiamata
+        // e se il programma non dichiara una funzione con lo stesso nome.
+        let declared_functions: Vec<&str> = self
+            .tokens
+            .windows(2)
+            .filter_map(|pair| match (&pair[0].token, &pair[1].token) {
+                (Token::Keyword(kw), Token::Identifier(name)) if kw == "function" => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+        // Dentro un pattern di `case` (fino alla guardia o ai ':') `insieme(...)` è un pattern di tipo
+        let mut pattern_depth: Option<i32> = None;
+        for pair in self.tokens.windows(2) {
+            let token_ws = &pair[0];
+            match (&token_ws.token, pattern_depth) {
+                (Token::Keyword(kw), _) if kw == "case" => pattern_depth = Some(0),
+                (Token::Delimiter(d), Some(depth)) if d == "(" || d == "[" || d == "{" => pattern_depth = Some(depth + 1),
+                (Token::Delimiter(d), Some(depth)) if (d == ")" || d == "]" || d == "}") && depth > 0 => pattern_depth = Some(depth - 1),
+                (Token::Delimiter(d), Some(0)) if d == ":" => pattern_depth = None,
+                (Token::Keyword(kw), Some(0)) if kw == "if" => pattern_depth = None,
+                _ => {}
+            }
+            if pair[1].token != Token::Delimiter("(".to_string()) {
+                continue;
+            }
             if let Token::Identifier(ref name) = token_ws.token {
+                if declared_functions.contains(&name.as_str()) {
+                    continue;
+                }
                 if let Some((canonical, module)) = translation_engine.get_builtin_info(name) {
-                    if !import_manager.is_member_active(canonical, module) {
-                        return Err(format!(
-                            "Import Error (Line {}, Col {}): Built-in function '{}' used but its library module '{}' was not imported",
-                            token_ws.line, token_ws.col, name, module
-                        ));
+                    if pattern_depth.is_some() && TYPE_PATTERNS.contains(&canonical) {
+                        continue;
+                    }
+                    if !import_manager.is_member_imported(canonical) {
+                        errors.push(ParseError {
+                            kind: "Import Error",
+                            line: token_ws.line,
+                            col: token_ws.col,
+                            message: format!(
+                                "Built-in function '{}' used but its library module '{}' was not imported",
+                                name, module
+                            ),
+                        });
                     }
                 }
             }
         }
 
         // Impedisce l'uso di keyword come nomi di variabili/funzioni
-        for i in 0..self.tokens.len() {
-            if let Token::Keyword(ref kw) = self.tokens[i].token {
-                if kw == "let" || kw == "const" {
-                    if i + 1 < self.tokens.len() {
-                        if let Token::Keyword(ref name) = self.tokens[i + 1].token {
-                            let line = self.tokens[i + 1].line;
-                            let col = self.tokens[i + 1].col;
-                            return Err(format!(
-                                "Syntax Error (Line {}, Col {}): Cannot use keyword '{}' as a variable name",
-                                line, col, name
-                            ));
-                        }
-                    }
-                }
-                if kw == "function" {
-                    if i + 1 < self.tokens.len() {
-                        if let Token::Keyword(ref name) = self.tokens[i + 1].token {
-                            let line = self.tokens[i + 1].line;
-                            let col = self.tokens[i + 1].col;
-                            return Err(format!(
-                                "Syntax Error (Line {}, Col {}): Cannot use keyword '{}' as a function name",
-                                line, col, name
-                            ));
-                        }
-                    }
-                }
+        for pair in self.tokens.windows(2) {
+            if let (Token::Keyword(ref kw), Token::Keyword(ref name)) = (&pair[0].token, &pair[1].token) {
+                let kind = match kw.as_str() {
+                    "let" | "const" => "variable",
+                    "function" => "function",
+                    _ => continue,
+                };
+                errors.push(ParseError::syntax(
+                    pair[1].line, pair[1].col,
+                    format!("Cannot use keyword '{}' as a {} name", name, kind),
+                ));
             }
         }
 
         // Evita blocchi graffiati spuri legati a chiamate o espressioni
-        for idx in 0..self.tokens.len() {
-            if let Token::Delimiter(ref sym) = self.tokens[idx].token {
-                if sym == "{" {
-                    if idx > 0 {
-                        let mut search_idx = idx - 1;
-                        if let Token::Delimiter(ref close_paren) = self.tokens[search_idx].token {
-                            if close_paren == ")" {
-                                let mut paren_stack = 1;
-                                while search_idx > 0 && paren_stack > 0 {
-                                    search_idx -= 1;
-                                    if let Token::Delimiter(ref p) = self.tokens[search_idx].token {
-                                        if p == ")" {
-                                            paren_stack += 1;
-                                        } else if p == "(" {
-                                            paren_stack -= 1;
-                                        }
-                                    }
-                                }
-                                if paren_stack == 0 && search_idx > 0 {
-                                    search_idx -= 1;
-                                    if let Token::Identifier(_) = &self.tokens[search_idx].token {
-                                        let mut is_function = false;
-                                        if search_idx > 0 {
-                                            if let Token::Keyword(ref kw) = &self.tokens[search_idx - 1].token {
-                                                if kw == "function" {
-                                                    is_function = true;
-                                                }
-                                            }
-                                        }
-                                        if !is_function {
-                                            let line = self.tokens[idx].line;
-                                            let col = self.tokens[idx].col;
-                                            return Err(format!(
-                                                "Syntax Error (Line {}, Col {}): Unexpected block '{{' following expression or function call",
-                                                line, col
-                                            ));
-                                        }
-                                    }
-                                }
-                            }
-                        }
+        let suggestions = crate::engine::suggest::Suggestions::new(translation_engine, import_manager);
+        self.spurious_blocks.clear();
+        for idx in 1..self.tokens.len() {
+            if self.tokens[idx].token != Token::Delimiter("{".to_string())
+                || self.tokens[idx - 1].token != Token::Delimiter(")".to_string())
+            {
+                continue;
+            }
+            let mut search_idx = idx - 1;
+            let mut paren_stack = 1;
+            while search_idx > 0 && paren_stack > 0 {
+                search_idx -= 1;
+                if let Token::Delimiter(ref p) = self.tokens[search_idx].token {
+                    if p == ")" {
+                        paren_stack += 1;
+                    } else if p == "(" {
+                        paren_stack -= 1;
+                    }
+                }
+            }
+            if paren_stack == 0 && search_idx > 0 {
+                search_idx -= 1;
+                if let Token::Identifier(ref name) = self.tokens[search_idx].token {
+                    let is_function = search_idx > 0
+                        && self.tokens[search_idx - 1].token == Token::Keyword("function".to_string());
+                    if !is_function && !self.ends_for_header(search_idx) {
+                        // Spesso è una keyword di una lingua non importata o scritta male (es. "mientras (...) {"
+                        // oppure "funzoine f() {", dove la keyword è l'identificatore precedente)
+                        let hint = match search_idx.checked_sub(1).map(|i| &self.tokens[i].token) {
+                            Some(Token::Identifier(previous)) => suggestions.for_words(previous, name),
+                            _ => suggestions.for_keyword(name),
+                        };
+                        let hint = hint.map(|h| format!("; {}", h)).unwrap_or_default();
+                        errors.push(ParseError::syntax(
+                            self.tokens[idx].line, self.tokens[idx].col,
+                            format!("Unexpected block '{{' following expression or function call{}", hint),
+                        ));
+                        self.spurious_blocks.push(idx);
                     }
                 }
             }
         }
 
         // Controllo del bilanciamento di parentesi tonde, quadre e graffe
-        let mut stack = Vec::new();
+        self.brace_pairs.clear();
+        let mut stack: Vec<(&str, usize)> = Vec::new();
         for (idx, token_ws) in self.tokens.iter().enumerate() {
-            if let Token::Delimiter(ref sym) = token_ws.token {
-                match sym.as_str() {
-                    "{" | "(" | "[" => {
-                        stack.push((sym.as_str(), idx));
-                    }
-                    "}" => {
-                        match stack.pop() {
-                            Some(("{", _)) => {}
-                            Some((expected, start_idx)) => {
-                                let line = token_ws.line;
-                                let col = token_ws.col;
-                                let opened_line = self.tokens[start_idx].line;
-                                let opened_col = self.tokens[start_idx].col;
-                                return Err(format!(
-                                    "Syntax Error (Line {}, Col {}): Mismatched closing brace '}}'. Expected closing for '{}' opened at Line {}, Col {}",
-                                    line, col, expected, opened_line, opened_col
-                                ));
-                            }
-                            None => {
-                                let line = token_ws.line;
-                                let col = token_ws.col;
-                                return Err(format!(
-                                    "Syntax Error (Line {}, Col {}): Unmatched closing brace '}}'",
-                                    line, col
-                                ));
-                            }
-                        }
-                    }
-                    ")" => {
-                        match stack.pop() {
-                            Some(("(", _)) => {}
-                            Some((expected, start_idx)) => {
-                                let line = token_ws.line;
-                                let col = token_ws.col;
-                                let opened_line = self.tokens[start_idx].line;
-                                let opened_col = self.tokens[start_idx].col;
-                                return Err(format!(
-                                    "Syntax Error (Line {}, Col {}): Mismatched closing parenthesis ')'. Expected closing for '{}' opened at Line {}, Col {}",
-                                    line, col, expected, opened_line, opened_col
-                                ));
-                            }
-                            None => {
-                                let line = token_ws.line;
-                                let col = token_ws.col;
-                                return Err(format!(
-                                    "Syntax Error (Line {}, Col {}): Unmatched closing parenthesis ')'",
-                                    line, col
-                                ));
-                            }
-                        }
-                    }
-                    "]" => {
-                        match stack.pop() {
-                            Some(("[", _)) => {}
-                            Some((expected, start_idx)) => {
-                                let line = token_ws.line;
-                                let col = token_ws.col;
-                                let opened_line = self.tokens[start_idx].line;
-                                let opened_col = self.tokens[start_idx].col;
-                                return Err(format!(
-                                    "Syntax Error (Line {}, Col {}): Mismatched closing bracket ']'. Expected closing for '{}' opened at Line {}, Col {}",
-                                    line, col, expected, opened_line, opened_col
-                                ));
-                            }
-                            None => {
-                                let line = token_ws.line;
-                                let col = token_ws.col;
-                                return Err(format!(
-                                    "Syntax Error (Line {}, Col {}): Unmatched closing bracket ']'",
-                                    line, col
-                                ));
-                            }
-                        }
+            let sym = match token_ws.token {
+                Token::Delimiter(ref sym) => sym.as_str(),
+                _ => continue,
+            };
+            let (opening, name) = match sym {
+                "{" | "(" | "[" => {
+                    stack.push((sym, idx));
+                    continue;
+                }
+                "}" => ("{", "brace"),
+                ")" => ("(", "parenthesis"),
+                "]" => ("[", "bracket"),
+                _ => continue,
+            };
+            match stack.pop() {
+                Some((open, start_idx)) if open == opening => {
+                    if open == "{" {
+                        self.brace_pairs.insert(start_idx, idx);
                     }
-                    _ => {}
+                }
+                Some((expected, start_idx)) => {
+                    errors.push(ParseError::syntax(
+                        token_ws.line, token_ws.col,
+                        format!(
+                            "Mismatched closing {} '{}'. Expected closing for '{}' opened at Line {}, Col {}",
+                            name, sym, expected, self.tokens[start_idx].line, self.tokens[start_idx].col
+                        ),
+                    ));
+                }
+                None => {
+                    errors.push(ParseError::syntax(
+                        token_ws.line, token_ws.col,
+                        format!("Unmatched closing {} '{}'", name, sym),
+                    ));
                 }
             }
         }
 
-        if let Some((unclosed, idx)) = stack.pop() {
-            let line = self.tokens[idx].line;
-            let col = self.tokens[idx].col;
-            return Err(format!(
-                "Syntax Error (Line {}, Col {}): Unclosed delimiter '{}'",
-                line, col, unclosed
+        for (unclosed, idx) in stack {
+            errors.push(ParseError::syntax(
+                self.tokens[idx].line, self.tokens[idx].col,
+                format!("Unclosed delimiter '{}'", unclosed),
             ));
         }
 
-        Ok(())
+        errors
+    }
+
+    /// Vero se l'espressione che inizia in `start` è l'iterabile di un `for x in ...`,
+    /// dove la graffa che segue la chiamata apre il corpo del ciclo.
+    fn ends_for_header(&self, start: usize) -> bool {
+        let mut depth = 0;
+        for idx in (0..start).rev() {
+            match &self.tokens[idx].token {
+                Token::Delimiter(d) if d == ")" || d == "]" => depth += 1,
+                Token::Delimiter(d) if d == "(" || d == "[" => {
+                    if depth == 0 {
+                        return false;
+                    }
+                    depth -= 1;
+                }
+                Token::Delimiter(d) if d == "{" || d == "}" => return false,
+                Token::Keyword(kw) if kw == "in" && depth == 0 => {
+                    let before = |n: usize| idx.checked_sub(n).map(|i| &self.tokens[i].token);
+                    let is_for = |t: Option<&Token>| t == Some(&Token::Keyword("for".to_string()));
+                    return matches!(before(1), Some(Token::Identifier(_)))
+                        && (is_for(before(2))
+                            || (before(2) == Some(&Token::Delimiter("(".to_string())) && is_for(before(3))));
+                }
+                _ => {}
+            }
+        }
+        false
     }
 }
 
@@ -280,41 +493,78 @@ mod tests {
 
     #[test]
     fn test_invalid_break_and_continue() {
-        let engine = Engine::new();
+        let mut engine = Engine::new();
         
         // interrompi fuori da un ciclo
         let source_break = "importa italiano da translate\ninterrompi\n";
         // Esegue il test validando gli import, la tokenizzazione ed il parsing
-        let (stripped, import_mgr) = crate::engine::check::validate_imports(source_break, &engine.translation_engine).unwrap();
+        let (stripped, import_mgr, _) = crate::engine::check::validate_imports(source_break, &mut engine.translation_engine);
         let filtered = crate::engine::filter::FilteredEngine::new(&engine.translation_engine, &import_mgr);
         let mut lexer = crate::engine::lexer::Lexer::new(&stripped);
         let tokens = lexer.tokenize(&engine.translation_engine, &filtered);
         let mut parser = crate::engine::parser::Parser::new(tokens);
         let res = parser.parse(&engine.translation_engine, &import_mgr);
         assert!(res.is_err());
-        assert!(res.unwrap_err().contains("only allowed inside loops"));
+        assert!(res.unwrap_err().iter().any(|e| e.contains("only allowed inside loops")));
 
         // continua fuori da un ciclo
         let source_continue = "importa italiano da translate\ncontinua\n";
-        let (stripped, import_mgr) = crate::engine::check::validate_imports(source_continue, &engine.translation_engine).unwrap();
+        let (stripped, import_mgr, _) = crate::engine::check::validate_imports(source_continue, &mut engine.translation_engine);
         let filtered = crate::engine::filter::FilteredEngine::new(&engine.translation_engine, &import_mgr);
         let mut lexer = crate::engine::lexer::Lexer::new(&stripped);
         let tokens = lexer.tokenize(&engine.translation_engine, &filtered);
         let mut parser = crate::engine::parser::Parser::new(tokens);
         let res = parser.parse(&engine.translation_engine, &import_mgr);
         assert!(res.is_err());
-        assert!(res.unwrap_err().contains("only allowed inside loops"));
+        assert!(res.unwrap_err().iter().any(|e| e.contains("only allowed inside loops")));
 
         // interrompi dentro una funzione all'interno di un ciclo (illegale)
         let source_func = "importa italiano da translate\nmentre (vero) {\nfunzione f() {\ninterrompi\n}\n}\n";
-        let (stripped, import_mgr) = crate::engine::check::validate_imports(source_func, &engine.translation_engine).unwrap();
+        let (stripped, import_mgr, _) = crate::engine::check::validate_imports(source_func, &mut engine.translation_engine);
         let filtered = crate::engine::filter::FilteredEngine::new(&engine.translation_engine, &import_mgr);
         let mut lexer = crate::engine::lexer::Lexer::new(&stripped);
         let tokens = lexer.tokenize(&engine.translation_engine, &filtered);
         let mut parser = crate::engine::parser::Parser::new(tokens);
         let res = parser.parse(&engine.translation_engine, &import_mgr);
         assert!(res.is_err());
-        assert!(res.unwrap_err().contains("only allowed inside loops"));
+        assert!(res.unwrap_err().iter().any(|e| e.contains("only allowed inside loops")));
+    }
+
+    #[test]
+    fn test_loop_labels() {
+        let mut engine = Engine::new();
+        let source = "importa italiano da translate\n\
+            esterno: per (i in 0..3) {\n\
+            ripeti {\n\
+            continua esterno\n\
+            } finche (vero)\n\
+            interrompi altro\n\
+            }\n\
+            fuori: se (vero) {\n\
+            }\n\
+            a: ciclo {\n\
+            a: mentre (vero) {\n\
+            interrompi a\n\
+            }\n\
+            funzione f() {\n\
+            ciclo {\n\
+            interrompi a\n\
+            }\n\
+            }\n\
+            }\n";
+        let (stripped, import_mgr, _) = crate::engine::check::validate_imports(source, &mut engine.translation_engine);
+        let filtered = crate::engine::filter::FilteredEngine::new(&engine.translation_engine, &import_mgr);
+        let mut lexer = crate::engine::lexer::Lexer::new(&stripped);
+        let tokens = lexer.tokenize(&engine.translation_engine, &filtered);
+        let mut parser = crate::engine::parser::Parser::new(tokens);
+        let errors = parser.parse(&engine.translation_engine, &import_mgr).unwrap_err();
+
+        // Le etichette valgono solo per i cicli che le racchiudono, e non dentro le funzioni
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors[0].contains("Line 6") && errors[0].ends_with("Unknown loop label 'altro'"));
+        assert!(errors[1].contains("Line 8") && errors[1].ends_with("Label 'fuori' must be followed by a loop"));
+        assert!(errors[2].contains("Line 11") && errors[2].ends_with("Label 'a' is already used by an enclosing loop"));
+        assert!(errors[3].contains("Line 16") && errors[3].ends_with("Unknown loop label 'a'"));
     }
 
     #[test]
@@ -322,9 +572,9 @@ mod tests {
         let mut engine = Engine::new();
         engine.quiet = true;
         
-        // 1. Test corto circuito (non deve sollevare errore per variabile indefinita)
-        let source_sc = "importa italiano da translate\ncrea x = falso && indefinita\ncrea y = vero || indefinita\n";
-        engine.run(source_sc);
+        // 1. Test corto circuito (il secondo operando, che lancerebbe un'eccezione, non deve essere valutato)
+        let source_sc = "importa italiano da translate\nfunzione esplodi() {\nlancia \"errore\"\n}\ncrea x = falso && esplodi()\ncrea y = vero || esplodi()\n";
+        assert!(engine.run(source_sc));
         assert!(engine.interpreter.exception.is_none());
 
         // 2. Test propagazione eccezione try-finally senza catch
@@ -338,4 +588,123 @@ mod tests {
         assert!(engine.interpreter.exception.is_none());
         assert_eq!(engine.interpreter.get_var("nome_val"), crate::engine::value::Value::String("mario".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ternary_and_null_coalescing_precedence() {
+        let mut engine = Engine::new();
+        engine.quiet = true;
+
+        // `??` lega meno di `||` e più del ternario
+        let source = "importa italiano da translate\ncrea m = {\"a\": nullo}\ncrea t = vero ? 1 : 2\ncrea c = m.a ?? falso || vero\ncrea d = m?.b ?? 0 ? \"si\" : \"no\"\n";
+        assert!(engine.run(source));
+        assert_eq!(engine.interpreter.get_var("t"), crate::engine::value::Value::Integer(1));
+        assert_eq!(engine.interpreter.get_var("c"), crate::engine::value::Value::Boolean(true));
+        assert_eq!(engine.interpreter.get_var("d"), crate::engine::value::Value::String("no".to_string()));
+
+        let source_call = "importa italiano da translate\ncrea m = {}\ncrea x = m?.f(1)\n";
+        assert!(!engine.run(source_call));
+    }
+
+    #[test]
+    fn test_generators_and_for_over_calls() {
+        let mut engine = Engine::new();
+        engine.quiet = true;
+
+        // Il generatore infinito produce solo i valori richiesti; la chiamata nell'intestazione del `per` non è un blocco spurio
+        let source = "importa italiano da translate\nimporta * da niter\nfunzione quadrati() {\ncrea n = 0\nciclo {\nproduci n * n\nn = n + 1\n}\n}\n\
+            crea somma = 0\nper q in prendi(salta(quadrati(), 1), 3) {\nsomma = somma + q\n}\n";
+        assert!(engine.run(source));
+        assert_eq!(engine.interpreter.get_var("somma"), crate::engine::value::Value::Integer(14));
+
+        let source_top = "importa italiano da translate\nproduci 1\n";
+        assert!(!engine.run(source_top));
+
+        // Senza l'import di niter gli adattatori sono nomi liberi, ma chiamarli è un errore di import
+        let source_free = "importa italiano da translate\ncrea prendi = 2\ncrea prossimo = prendi + 1\n";
+        assert!(engine.run(source_free));
+        assert_eq!(engine.interpreter.get_var("prossimo"), crate::engine::value::Value::Integer(3));
+        let source_call = "importa italiano da translate\ncrea x = raccogli([1])\n";
+        assert!(!engine.run(source_call));
+    }
+
+    #[test]
+    fn test_tasks_and_channels() {
+        let mut engine = Engine::new();
+        engine.quiet = true;
+
+        // Il task riceve una copia dell'array e comunica solo tramite il canale
+        let source = "importa italiano da translate\nimporta * da nconc\nimporta * da ndata\nfunzione somma(lista, uscita) {\naggiungi(lista, 10)\n\
+            invia(uscita, lista)\nritorna 1\n}\ncrea l = [1]\ncrea c = canale()\ncrea t = avvia(somma, [l, c])\n\
+            crea ricevuta = ricevi(c)\ncrea r = attendi(t)\n";
+        assert!(engine.run(source));
+        assert_eq!(engine.interpreter.get_var("l").to_string(), "[1]");
+        assert_eq!(engine.interpreter.get_var("ricevuta").to_string(), "[1, 10]");
+        assert_eq!(engine.interpreter.get_var("r"), crate::engine::value::Value::Integer(1));
+
+        // Nessun altro task può trasmettere: l'attesa termina con un errore invece di bloccare il programma
+        let source_deadlock = "importa italiano da translate\nimporta * da nconc\nfunzione aspetta(c) {\nritorna ricevi(c)\n}\n\
+            crea t = avvia(aspetta, [canale()])\nattendi(t)\n";
+        assert!(!engine.run(source_deadlock));
+        assert!(engine.interpreter.exception.as_ref().is_some_and(|e| e.to_string().contains("Deadlock")));
+    }
+
+    #[test]
+    fn test_spurious_block_suggests_keyword() {
+        let mut engine = Engine::new();
+        let source = "importa italiano da translate\ncrea i = 0\nmientras (i < 3) {\ncrea x = 1\n}\nfunzoine f() {\nritorna 1\n}\n";
+        let (stripped, import_mgr, _) = crate::engine::check::validate_imports(source, &mut engine.translation_engine);
+        let filtered = crate::engine::filter::FilteredEngine::new(&engine.translation_engine, &import_mgr);
+        let mut lexer = crate::engine::lexer::Lexer::new(&stripped);
+        let tokens = lexer.tokenize(&engine.translation_engine, &filtered);
+        let mut parser = crate::engine::parser::Parser::new(tokens);
+        let errors = parser.parse(&engine.translation_engine, &import_mgr).unwrap_err();
+
+        // I blocchi spuri vengono segnalati una sola volta, senza errori a cascata al loro interno
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].contains("Line 3") && errors[0].ends_with("'mientras' is the spanish keyword 'while', add 'importa spagnolo da translate' to use it"));
+        assert!(errors[1].contains("Line 6") && errors[1].ends_with("did you mean 'funzione'?"));
+    }
+
+    #[test]
+    fn test_error_recovery_reports_every_error() {
+        let mut engine = Engine::new();
+
+        // Tre errori indipendenti: nome mancante, espressione malformata in un blocco, ']' spuria
+        let source = "importa italiano da translate\ncrea = 5\nse (vero) {\ncrea y = * 2\ncrea z = 1\n}\ncrea w = 3 ]\n";
+        let (stripped, import_mgr, _) = crate::engine::check::validate_imports(source, &mut engine.translation_engine);
+        let filtered = crate::engine::filter::FilteredEngine::new(&engine.translation_engine, &import_mgr);
+        let mut lexer = crate::engine::lexer::Lexer::new(&stripped);
+        let tokens = lexer.tokenize(&engine.translation_engine, &filtered);
+        let mut parser = crate::engine::parser::Parser::new(tokens);
+        let errors = parser.parse(&engine.translation_engine, &import_mgr).unwrap_err();
+
+        assert!(errors.iter().any(|e| e.contains("Line 2") && e.contains("Expected identifier for variable name")));
+        assert!(errors.iter().any(|e| e.contains("Line 4") && e.contains("Unexpected token")));
+        assert!(errors.iter().any(|e| e.contains("Line 7") && e.contains("Unmatched closing bracket")));
+        // La risincronizzazione sulla '}' del blocco non deve produrre errori a cascata
+        assert!(!errors.iter().any(|e| e.contains("Line 5") || e.contains("Line 6")));
+        // Ordinati per posizione, senza duplicare la ']' segnalata sia dai controlli preliminari che dal parser
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].contains("Line 2") && errors[1].contains("Line 4") && errors[2].contains("Line 7"));
+    }
+
+    #[test]
+    fn test_rest_pattern_spelling() {
+        let mut engine = Engine::new();
+
+        // Il resto di un pattern di array si scrive solo `...`; `..` è segnalato con il suggerimento
+        let source = "importa italiano da translate
+crea [a, ...b] = [1, 2]
+crea [c, ..d] = [1, 2]
+";
+        let (stripped, import_mgr, _) = crate::engine::check::validate_imports(source, &mut engine.translation_engine);
+        let filtered = crate::engine::filter::FilteredEngine::new(&engine.translation_engine, &import_mgr);
+        let mut lexer = crate::engine::lexer::Lexer::new(&stripped);
+        let tokens = lexer.tokenize(&engine.translation_engine, &filtered);
+        let mut parser = crate::engine::parser::Parser::new(tokens);
+        let errors = parser.parse(&engine.translation_engine, &import_mgr).unwrap_err();
+
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("Line 3") && errors[0].ends_with("The rest pattern is written '...', not '..'"));
+    }
+}
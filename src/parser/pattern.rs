@@ -0,0 +1,219 @@
+use crate::engine::ast::{Expression, MatchArm, Pattern};
+use crate::engine::lexer::Token;
+use super::{ParseError, Parser};
+
+/// Keyword che in un pattern indicano un tipo (canonical, uguali a `Value::type_name`).
+/// Fuori dai pattern i nomi dei tipi sono identificatori liberi (vedi `CONTEXTUAL_KEYWORDS`).
+pub const TYPE_PATTERNS: &[&str] = &["boolean", "integer", "float", "decimal", "string", "array", "map", "set", "record"];
+
+impl Parser {
+    /// Analizza un'espressione `match (valore) { case pattern: valore ... default: valore }`.
+    pub fn parse_match(&mut self) -> Result<Expression, ParseError> {
+        self.advance(); // consuma match
+        let subject = self.parse_expression()?;
+        self.consume(&Token::Delimiter("{".to_string()), "Expected '{' before match arms")?;
+
+        let mut arms = Vec::new();
+        let mut default = None;
+        while self.current_token() != &Token::Delimiter("}".to_string()) && self.current_token() != &Token::EOF {
+            match self.current_token() {
+                Token::Keyword(ref kw) if kw == "case" => {
+                    self.advance();
+                    let pattern = self.parse_pattern()?;
+                    self.report_duplicate_bindings(&pattern);
+                    let mut guard = None;
+                    if self.current_token() == &Token::Keyword("if".to_string()) {
+                        self.advance();
+                        guard = Some(self.parse_expression()?);
+                    }
+                    self.consume(&Token::Delimiter(":".to_string()), "Expected ':' after match pattern")?;
+                    let value = self.parse_expression()?;
+                    arms.push(MatchArm { pattern, guard, value });
+                }
+                Token::Keyword(ref kw) if kw == "default" => {
+                    if default.is_some() {
+                        return Err(self.error("Duplicate 'default' arm in match"));
+                    }
+                    self.advance();
+                    self.consume(&Token::Delimiter(":".to_string()), "Expected ':' after default")?;
+                    default = Some(Box::new(self.parse_expression()?));
+                }
+                _ => {
+                    return Err(self.error(&format!(
+                        "Expected 'case' or 'default' inside match block, found {}",
+                        self.current_token()
+                    )));
+                }
+            }
+        }
+        self.consume(&Token::Delimiter("}".to_string()), "Expected '}' at end of match block")?;
+        Ok(Expression::Match { subject: Box::new(subject), arms, default })
+    }
+
+    /// Segnala le variabili legate più volte dallo stesso pattern (es. `[x, x]`).
+    pub fn report_duplicate_bindings(&mut self, pattern: &Pattern) {
+        let mut seen: Vec<&str> = Vec::new();
+        for name in pattern.bindings() {
+            if seen.contains(&name) {
+                let err = self.error(&format!("Variable '{}' is bound more than once in the same pattern", name));
+                self.errors.push(err);
+            }
+            seen.push(name);
+        }
+    }
+
+    /// Pattern completo: alternative separate da `|`, ciascuna con le stesse variabili.
+    pub fn parse_pattern(&mut self) -> Result<Pattern, ParseError> {
+        let mut options = vec![self.parse_single_pattern()?];
+        while self.current_token() == &Token::Operator("|".to_string()) {
+            self.advance();
+            options.push(self.parse_single_pattern()?);
+        }
+
+        let pattern = if options.len() == 1 {
+            options.remove(0)
+        } else {
+            let mut expected = options[0].bindings();
+            expected.sort();
+            for option in &options[1..] {
+                let mut names = option.bindings();
+                names.sort();
+                if names != expected {
+                    let err = self.error("All alternatives of a pattern must bind the same variables");
+                    self.errors.push(err);
+                }
+            }
+            Pattern::Alternatives(options)
+        };
+        Ok(pattern)
+    }
+
+    fn parse_single_pattern(&mut self) -> Result<Pattern, ParseError> {
+        let token = match self.current_token().clone() {
+            // Un nome di tipo scritto come identificatore (es. "intero") è un pattern di tipo
+            Token::Identifier(name) => match self.contextual_keyword(&name, TYPE_PATTERNS) {
+                Some(canonical) => Token::Keyword(canonical),
+                None => Token::Identifier(name),
+            },
+            other => other,
+        };
+        match token {
+            Token::Identifier(name) => {
+                self.advance();
+                if self.current_token() == &Token::Delimiter("(".to_string()) {
+                    self.advance();
+                    let fields = self.parse_field_patterns()?;
+                    return Ok(Pattern::Record { name, fields });
+                }
+                if name == "_" {
+                    Ok(Pattern::Wildcard)
+                } else {
+                    Ok(Pattern::Binding(name))
+                }
+            }
+            Token::Keyword(ref kw) if TYPE_PATTERNS.contains(&kw.as_str()) => {
+                self.advance();
+                let mut inner = None;
+                if self.current_token() == &Token::Delimiter("(".to_string()) {
+                    self.advance();
+                    inner = Some(Box::new(self.parse_pattern()?));
+                    self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after type pattern")?;
+                }
+                Ok(Pattern::Type { name: kw.clone(), inner })
+            }
+            Token::Delimiter(ref sym) if sym == "[" => {
+                self.advance();
+                let mut items = Vec::new();
+                let mut rest = None;
+                while self.current_token() != &Token::Delimiter("]".to_string()) {
+                    // Un elemento dopo il resto è segnalato ma non interrompe l'analisi del pattern
+                    if rest.is_some() {
+                        let err = self.error("The '...' rest pattern must be the last element of an array pattern");
+                        self.errors.push(err);
+                    }
+                    if matches!(self.current_token(), Token::Operator(op) if op == "..." || op == "..") {
+                        // `..` è l'intervallo: il resto si scrive sempre `...`, come nelle destrutturazioni
+                        if self.current_token() == &Token::Operator("..".to_string()) {
+                            let err = self.error("The rest pattern is written '...', not '..'");
+                            self.errors.push(err);
+                        }
+                        self.advance();
+                        rest = Some(Box::new(match self.current_token().clone() {
+                            Token::Identifier(name) if name != "_" => {
+                                self.advance();
+                                Pattern::Binding(name)
+                            }
+                            Token::Identifier(_) => {
+                                self.advance();
+                                Pattern::Wildcard
+                            }
+                            _ => Pattern::Wildcard,
+                        }));
+                    } else {
+                        items.push(self.parse_pattern()?);
+                    }
+                    if self.current_token() == &Token::Delimiter(",".to_string()) {
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+                self.consume(&Token::Delimiter("]".to_string()), "Expected ']' at end of array pattern")?;
+                Ok(Pattern::Array { items, rest })
+            }
+            Token::Delimiter(ref sym) if sym == "{" => {
+                self.advance();
+                let mut entries = Vec::new();
+                while self.current_token() != &Token::Delimiter("}".to_string()) {
+                    let (key, is_name) = match self.current_token() {
+                        Token::Identifier(s) => (s.clone(), true),
+                        Token::StringLiteral(s) => (s.clone(), false),
+                        Token::Keyword(k) => (k.clone(), false),
+                        _ => return Err(self.error("Expected string or identifier as key in map pattern")),
+                    };
+                    self.advance();
+                    // `{nome}` abbrevia `{"nome": nome}`
+                    let shorthand = is_name
+                        && matches!(self.current_token(), Token::Delimiter(d) if d == "," || d == "}");
+                    if shorthand {
+                        entries.push((key.clone(), Pattern::Binding(key)));
+                    } else {
+                        self.consume(&Token::Delimiter(":".to_string()), "Expected ':' after map pattern key")?;
+                        entries.push((key, self.parse_pattern()?));
+                    }
+                    if self.current_token() == &Token::Delimiter(",".to_string()) {
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+                self.consume(&Token::Delimiter("}".to_string()), "Expected '}' at end of map pattern")?;
+                Ok(Pattern::Map(entries))
+            }
+            _ => {
+                // Valore letterale, eventualmente estremo iniziale di un intervallo
+                let start = self.parse_term()?;
+                if self.current_token() == &Token::Operator("..".to_string()) {
+                    self.advance();
+                    let end = self.parse_term()?;
+                    return Ok(Pattern::Range { start, end });
+                }
+                Ok(Pattern::Literal(start))
+            }
+        }
+    }
+
+    /// Pattern dei campi di un record, separati da virgole, fino alla ')' di chiusura.
+    fn parse_field_patterns(&mut self) -> Result<Vec<Pattern>, ParseError> {
+        let mut patterns = Vec::new();
+        if self.current_token() != &Token::Delimiter(")".to_string()) {
+            patterns.push(self.parse_pattern()?);
+            while self.current_token() == &Token::Delimiter(",".to_string()) {
+                self.advance();
+                patterns.push(self.parse_pattern()?);
+            }
+        }
+        self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after record pattern fields")?;
+        Ok(patterns)
+    }
+}
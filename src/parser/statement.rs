@@ -1,11 +1,12 @@
-use crate::engine::ast::{Statement, Expression};
+use crate::engine::ast::{Block, Statement, Expression, Params};
 use crate::engine::lexer::Token;
-use super::Parser;
+use super::{ParseError, Parser};
 
 impl Parser {
     /// Analizza un singolo statement.
-    pub fn parse_statement(&mut self) -> Result<Statement, String> {
+    pub fn parse_statement(&mut self) -> Result<Statement, ParseError> {
         let token = self.current_token().clone();
+        let line = self.current_location().0;
         match token {
             Token::Keyword(ref kw) => {
                 match kw.as_str() {
@@ -14,55 +15,87 @@ impl Parser {
                         self.parse_var_decl(is_mut)
                     }
                     "if" => self.parse_if_statement(),
-                    "while" => self.parse_while_statement(),
-                    "for" => self.parse_for_statement(),
+                    "while" | "for" | "do" | "repeat" | "loop" => self.parse_loop(None),
                     "switch" => self.parse_switch_statement(),
                     "try" => self.parse_try_catch_statement(),
                     "throw" => self.parse_throw_statement(),
                     "break" => {
                         if self.loop_depth == 0 {
-                            return Err("Syntax Error: 'break' (interrompi) is only allowed inside loops".to_string());
+                            return Err(self.error("'break' (interrompi) is only allowed inside loops"));
                         }
                         self.advance();
-                        Ok(Statement::Break)
+                        let label = self.parse_jump_label(line)?;
+                        Ok(Statement::Break { label, line })
                     }
                     "continue" => {
                         if self.loop_depth == 0 {
-                            return Err("Syntax Error: 'continue' (continua) is only allowed inside loops".to_string());
+                            return Err(self.error("'continue' (continua) is only allowed inside loops"));
                         }
                         self.advance();
-                        Ok(Statement::Continue)
+                        let label = self.parse_jump_label(line)?;
+                        Ok(Statement::Continue { label, line })
                     }
                     "return" => self.parse_return_statement(),
+                    "yield" => {
+                        if !self.in_function {
+                            return Err(self.error("'yield' (produci) is only allowed inside functions"));
+                        }
+                        self.advance();
+                        let value = self.parse_expression()?;
+                        Ok(Statement::Yield { value, line })
+                    }
                     "function" => self.parse_function(),
+                    "record" => self.parse_record(),
                     _ => {
                         let expr = self.parse_expression()?;
-                        Ok(Statement::Expr(expr))
+                        Ok(Statement::Expr { expr, line })
                     }
                 }
             }
+            Token::Identifier(ref name) if self.peek_token() == &Token::Delimiter(":".to_string()) => {
+                let label = name.clone();
+                self.advance();
+                self.advance();
+                if !matches!(self.current_token(), Token::Keyword(kw) if ["while", "for", "do", "repeat", "loop"].contains(&kw.as_str())) {
+                    return Err(self.error(&format!("Label '{}' must be followed by a loop", label)));
+                }
+                if self.loop_labels.contains(&label) {
+                    return Err(self.error(&format!("Label '{}' is already used by an enclosing loop", label)));
+                }
+                self.parse_loop(Some(label))
+            }
             Token::Identifier(_) => self.parse_identifier_statement(),
-            Token::EOF => Err("Syntax Error: Unexpected End Of File".to_string()),
+            Token::EOF => Err(self.error("Unexpected End Of File")),
             _ => {
                 let expr = self.parse_expression()?;
-                Ok(Statement::Expr(expr))
+                Ok(Statement::Expr { expr, line })
             }
         }
     }
 
-    fn parse_var_decl(&mut self, is_mutable: bool) -> Result<Statement, String> {
+    fn parse_var_decl(&mut self, is_mutable: bool) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         self.advance();
+        // Destrutturazione di un array o di una mappa
+        if matches!(self.current_token(), Token::Delimiter(d) if d == "[" || d == "{") {
+            let pattern = self.parse_pattern()?;
+            self.report_duplicate_bindings(&pattern);
+            self.consume(&Token::Operator("=".to_string()), "Expected '=' after destructuring pattern")?;
+            let value = self.parse_expression()?;
+            return Ok(Statement::PatternDecl { is_mutable, pattern, value, line });
+        }
         let name = match self.current_token() {
             Token::Identifier(s) => s.clone(),
-            _ => return Err("Expected identifier for variable name".to_string()),
+            _ => return Err(self.error("Expected identifier for variable name")),
         };
         self.advance();
         self.consume(&Token::Operator("=".to_string()), "Expected '=' after variable name")?;
         let value = self.parse_expression()?;
-        Ok(Statement::VarDecl { is_mutable, name, value })
+        Ok(Statement::VarDecl { is_mutable, name, value, line })
     }
 
-    fn parse_if_statement(&mut self) -> Result<Statement, String> {
+    fn parse_if_statement(&mut self) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         self.advance();
         let has_paren = self.current_token() == &Token::Delimiter("(".to_string());
         if has_paren {
@@ -78,17 +111,73 @@ impl Parser {
         if self.current_token() == &Token::Keyword("else".to_string()) {
             self.advance(); // consume else
             if self.current_token() == &Token::Keyword("if".to_string()) {
-                else_branch = Some(vec![self.parse_if_statement()?]);
+                else_branch = Some([self.parse_if_statement()?].into());
             } else {
                 self.consume(&Token::Delimiter("{".to_string()), "Expected '{' before else branch")?;
                 else_branch = Some(self.parse_block()?);
             }
         }
-        Ok(Statement::IfStatement { condition, then_branch, else_branch })
+        Ok(Statement::IfStatement { condition, then_branch, else_branch, line })
     }
 
-    fn parse_while_statement(&mut self) -> Result<Statement, String> {
+    /// Analizza un ciclo (while, for, do-while, repeat-until o loop), con l'etichetta facoltativa già consumata.
+    fn parse_loop(&mut self, label: Option<String>) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
+        let keyword = match self.current_token() {
+            Token::Keyword(kw) => kw.clone(),
+            _ => return Err(self.error("Expected a loop")),
+        };
+        match keyword.as_str() {
+            "while" => self.parse_while_statement(label),
+            "for" => self.parse_for_statement(label),
+            "loop" => {
+                self.advance();
+                let body = self.parse_loop_body("loop", &label)?;
+                Ok(Statement::WhileStatement { condition: Expression::LiteralBool(true), body, label, line })
+            }
+            _ => {
+                // do { } while (c) / repeat { } until (c)
+                self.advance();
+                let body = self.parse_loop_body(&keyword, &label)?;
+                let closing = if keyword == "do" { "while" } else { "until" };
+                self.consume(&Token::Keyword(closing.to_string()), &format!("Expected '{}' after '{}' body", closing, keyword))?;
+                let mut condition = self.parse_condition()?;
+                if keyword == "repeat" {
+                    condition = Expression::UnaryOp { operator: "!".to_string(), operand: Box::new(condition) };
+                }
+                Ok(Statement::DoWhileStatement { body, condition, label, line })
+            }
+        }
+    }
+
+    /// Etichetta facoltativa dopo break/continue: un identificatore sulla stessa riga.
+    fn parse_jump_label(&mut self, line: usize) -> Result<Option<String>, ParseError> {
+        let name = match self.current_token() {
+            Token::Identifier(name) if self.current_location().0 == line => name.clone(),
+            _ => return Ok(None),
+        };
+        if !self.loop_labels.contains(&name) {
+            return Err(self.error(&format!("Unknown loop label '{}'", name)));
+        }
         self.advance();
+        Ok(Some(name))
+    }
+
+    /// Corpo di un ciclo dalla '{', con l'etichetta visibile a break e continue al suo interno.
+    fn parse_loop_body(&mut self, keyword: &str, label: &Option<String>) -> Result<Block, ParseError> {
+        self.consume(&Token::Delimiter("{".to_string()), &format!("Expected '{{' before {} body", keyword))?;
+        self.loop_depth += 1;
+        self.loop_labels.extend(label.clone());
+        let body_res = self.parse_block();
+        if label.is_some() {
+            self.loop_labels.pop();
+        }
+        self.loop_depth -= 1;
+        body_res
+    }
+
+    /// Condizione di un ciclo, con le parentesi facoltative.
+    fn parse_condition(&mut self) -> Result<Expression, ParseError> {
         let has_paren = self.current_token() == &Token::Delimiter("(".to_string());
         if has_paren {
             self.advance();
@@ -97,15 +186,19 @@ impl Parser {
         if has_paren {
             self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after condition")?;
         }
-        self.consume(&Token::Delimiter("{".to_string()), "Expected '{' before while body")?;
-        self.loop_depth += 1;
-        let body_res = self.parse_block();
-        self.loop_depth -= 1;
-        let body = body_res?;
-        Ok(Statement::WhileStatement { condition, body })
+        Ok(condition)
     }
 
-    fn parse_for_statement(&mut self) -> Result<Statement, String> {
+    fn parse_while_statement(&mut self, label: Option<String>) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
+        self.advance();
+        let condition = self.parse_condition()?;
+        let body = self.parse_loop_body("while", &label)?;
+        Ok(Statement::WhileStatement { condition, body, label, line })
+    }
+
+    fn parse_for_statement(&mut self, label: Option<String>) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         self.advance();
         let has_paren = self.current_token() == &Token::Delimiter("(".to_string());
         if has_paren {
@@ -114,7 +207,7 @@ impl Parser {
 
         let iterator = match self.current_token() {
             Token::Identifier(s) => s.clone(),
-            _ => return Err("Expected identifier in for loop iterator".to_string()),
+            _ => return Err(self.error("Expected identifier in for loop iterator")),
         };
         self.advance();
 
@@ -122,26 +215,23 @@ impl Parser {
         let start = self.parse_expression()?;
 
         // Gestisce l'operatore opzionale di intervallo '..'
-        let mut end = start.clone();
+        let mut end = None;
         if let Token::Operator(ref op) = self.current_token() {
             if op == ".." {
                 self.advance();
-                end = self.parse_expression()?;
+                end = Some(self.parse_expression()?);
             }
         }
 
         if has_paren {
             self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after for loop range")?;
         }
-        self.consume(&Token::Delimiter("{".to_string()), "Expected '{' before for body")?;
-        self.loop_depth += 1;
-        let body_res = self.parse_block();
-        self.loop_depth -= 1;
-        let body = body_res?;
-        Ok(Statement::ForStatement { iterator, start, end, body })
+        let body = self.parse_loop_body("for", &label)?;
+        Ok(Statement::ForStatement { iterator, start, end, body, label, line })
     }
 
-    fn parse_switch_statement(&mut self) -> Result<Statement, String> {
+    fn parse_switch_statement(&mut self) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         self.advance();
         let has_paren = self.current_token() == &Token::Delimiter("(".to_string());
         if has_paren {
@@ -152,52 +242,69 @@ impl Parser {
             self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after switch value")?;
         }
         self.consume(&Token::Delimiter("{".to_string()), "Expected '{' before switch block")?;
+        self.enter_block();
 
         let mut cases = Vec::new();
         let mut default_case = None;
 
         while self.current_token() != &Token::Delimiter("}".to_string()) && self.current_token() != &Token::EOF {
-            match self.current_token() {
-                Token::Keyword(ref kw) if kw == "case" => {
-                    self.advance();
-                    let test = self.parse_expression()?;
-                    self.consume(&Token::Delimiter(":".to_string()), "Expected ':' after case value")?;
-                    let mut body = Vec::new();
-                    while self.current_token() != &Token::Keyword("case".to_string())
-                        && self.current_token() != &Token::Keyword("default".to_string())
-                        && self.current_token() != &Token::Delimiter("}".to_string())
-                        && self.current_token() != &Token::EOF
-                    {
-                        body.push(self.parse_statement()?);
-                    }
-                    cases.push((test, body));
-                }
-                Token::Keyword(ref kw) if kw == "default" => {
-                    self.advance();
-                    self.consume(&Token::Delimiter(":".to_string()), "Expected ':' after default")?;
-                    let mut body = Vec::new();
-                    while self.current_token() != &Token::Keyword("case".to_string())
-                        && self.current_token() != &Token::Keyword("default".to_string())
-                        && self.current_token() != &Token::Delimiter("}".to_string())
-                        && self.current_token() != &Token::EOF
-                    {
-                        body.push(self.parse_statement()?);
-                    }
-                    default_case = Some(body);
-                }
-                _ => {
-                    return Err(format!(
-                        "Expected 'case' or 'default' inside switch block, found {:?}",
-                        self.current_token()
-                    ));
-                }
+            let start = self.position;
+            if let Err(err) = self.parse_switch_arm(&mut cases, &mut default_case) {
+                self.errors.push(err);
+                self.synchronize(start);
             }
         }
+        self.exit_block();
         self.consume(&Token::Delimiter("}".to_string()), "Expected '}' at end of switch block")?;
-        Ok(Statement::SwitchStatement { discriminant, cases, default_case })
+        Ok(Statement::SwitchStatement { discriminant, cases, default_case, line })
     }
 
-    fn parse_try_catch_statement(&mut self) -> Result<Statement, String> {
+    /// Analizza un singolo ramo 'case' o 'default' di uno switch.
+    fn parse_switch_arm(
+        &mut self,
+        cases: &mut Vec<(Expression, Block)>,
+        default_case: &mut Option<Block>,
+    ) -> Result<(), ParseError> {
+        let test = match self.current_token() {
+            Token::Keyword(ref kw) if kw == "case" => {
+                self.advance();
+                let test = self.parse_expression()?;
+                self.consume(&Token::Delimiter(":".to_string()), "Expected ':' after case value")?;
+                Some(test)
+            }
+            Token::Keyword(ref kw) if kw == "default" => {
+                self.advance();
+                self.consume(&Token::Delimiter(":".to_string()), "Expected ':' after default")?;
+                None
+            }
+            _ => {
+                return Err(self.error(&format!(
+                    "Expected 'case' or 'default' inside switch block, found {}",
+                    self.current_token()
+                )));
+            }
+        };
+
+        let mut body = Vec::new();
+        while self.current_token() != &Token::Keyword("case".to_string())
+            && self.current_token() != &Token::Keyword("default".to_string())
+            && self.current_token() != &Token::Delimiter("}".to_string())
+            && self.current_token() != &Token::EOF
+        {
+            if let Some(stmt) = self.parse_statement_or_recover() {
+                body.push(stmt);
+            }
+        }
+
+        match test {
+            Some(test) => cases.push((test, body.into())),
+            None => *default_case = Some(body.into()),
+        }
+        Ok(())
+    }
+
+    fn parse_try_catch_statement(&mut self) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         self.advance();
         self.consume(&Token::Delimiter("{".to_string()), "Expected '{' after 'try'")?;
         let try_block = self.parse_block()?;
@@ -228,74 +335,157 @@ impl Parser {
         }
 
         if catch_block.is_none() && finally_block.is_none() {
-            return Err("Syntax Error: 'try' statement must have a 'catch' or 'finally' block".to_string());
+            return Err(self.error("'try' statement must have a 'catch' or 'finally' block"));
         }
 
-        Ok(Statement::TryCatchStatement { try_block, catch_variable, catch_block, finally_block })
+        Ok(Statement::TryCatchStatement { try_block, catch_variable, catch_block, finally_block, line })
     }
 
-    fn parse_throw_statement(&mut self) -> Result<Statement, String> {
+    fn parse_throw_statement(&mut self) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         self.advance();
         let value = self.parse_expression()?;
-        Ok(Statement::ThrowStatement { value })
+        Ok(Statement::ThrowStatement { value, line })
     }
 
-    fn parse_return_statement(&mut self) -> Result<Statement, String> {
+    fn parse_return_statement(&mut self) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         self.advance();
         let value = self.parse_expression()?;
-        Ok(Statement::ReturnStatement { value })
+        Ok(Statement::ReturnStatement { value, line })
     }
 
-    fn parse_function(&mut self) -> Result<Statement, String> {
+    fn parse_function(&mut self) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         self.advance();
         let name = match self.current_token() {
             Token::Identifier(s) => s.clone(),
-            _ => return Err("Expected identifier for function name".to_string()),
+            _ => return Err(self.error("Expected identifier for function name")),
         };
         self.advance();
         self.consume(&Token::Delimiter("(".to_string()), "Expected '(' after function name")?;
-        let mut params = Vec::new();
-        if self.current_token() != &Token::Delimiter(")".to_string()) {
-            if let Token::Identifier(ref p) = self.current_token() {
-                params.push(p.clone());
+        let (params, rest) = self.parse_parameters(&name)?;
+        self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after parameters")?;
+        self.consume(&Token::Delimiter("{".to_string()), "Expected '{' before function body")?;
+        // break, continue e le etichette non attraversano il corpo di una funzione
+        let old_loop_depth = std::mem::take(&mut self.loop_depth);
+        let old_loop_labels = std::mem::take(&mut self.loop_labels);
+        let old_in_function = std::mem::replace(&mut self.in_function, true);
+        let body_res = self.parse_block();
+        self.loop_depth = old_loop_depth;
+        self.loop_labels = old_loop_labels;
+        self.in_function = old_in_function;
+        let body = body_res?;
+        let is_generator = body.iter().any(Statement::yields);
+        Ok(Statement::FunctionDecl { name, params, rest, body, is_generator, line })
+    }
+
+    /// Parametri di una funzione fino alla ')': `a, b = predefinito, ...resto`.
+    /// Come nei record, i parametri con un valore predefinito sono gli ultimi; il parametro `...` chiude la lista.
+    fn parse_parameters(&mut self, function: &str) -> Result<(Params, Option<String>), ParseError> {
+        let mut params: Params = Vec::new();
+        let mut rest = None;
+        while self.current_token() != &Token::Delimiter(")".to_string()) {
+            if rest.is_some() {
+                return Err(self.error("The '...' rest parameter must be the last parameter"));
+            }
+            let is_rest = self.current_token() == &Token::Operator("...".to_string());
+            if is_rest {
                 self.advance();
             }
-            while self.current_token() == &Token::Delimiter(",".to_string()) {
+            let param = match self.current_token() {
+                Token::Identifier(s) => s.clone(),
+                _ => return Err(self.error("Expected parameter name")),
+            };
+            if params.iter().any(|(existing, _)| *existing == param) || rest.as_ref() == Some(&param) {
+                return Err(self.error(&format!("Duplicate parameter '{}' in function '{}'", param, function)));
+            }
+            self.advance();
+            if is_rest {
+                rest = Some(param);
+            } else if self.current_token() == &Token::Operator("=".to_string()) {
                 self.advance();
-                if let Token::Identifier(ref p) = self.current_token() {
-                    params.push(p.clone());
-                    self.advance();
+                params.push((param, Some(self.parse_expression()?)));
+            } else {
+                if params.iter().any(|(_, default)| default.is_some()) {
+                    return Err(self.error(&format!("Parameter '{}' without a default value follows a parameter with a default", param)));
                 }
+                params.push((param, None));
+            }
+            if self.current_token() == &Token::Delimiter(",".to_string()) {
+                self.advance();
+            } else {
+                break;
             }
         }
-        self.consume(&Token::Delimiter(")".to_string()), "Expected ')' after parameters")?;
-        self.consume(&Token::Delimiter("{".to_string()), "Expected '{' before function body")?;
-        let old_loop_depth = self.loop_depth;
-        self.loop_depth = 0;
-        let body_res = self.parse_block();
-        self.loop_depth = old_loop_depth;
-        let body = body_res?;
-        Ok(Statement::FunctionDecl { name, params, body })
+        Ok((params, rest))
+    }
+
+    /// Analizza `record Nome { campo, campo = predefinito }`; le virgole tra i campi sono facoltative.
+    fn parse_record(&mut self) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
+        self.advance();
+        let name = match self.current_token() {
+            Token::Identifier(s) => s.clone(),
+            _ => return Err(self.error("Expected identifier for record name")),
+        };
+        self.advance();
+        self.consume(&Token::Delimiter("{".to_string()), "Expected '{' after record name")?;
+        let mut fields: Params = Vec::new();
+        while self.current_token() != &Token::Delimiter("}".to_string()) {
+            let field = match self.current_token() {
+                Token::Identifier(s) => s.clone(),
+                _ => return Err(self.error("Expected field name in record declaration")),
+            };
+            if fields.iter().any(|(existing, _)| *existing == field) {
+                return Err(self.error(&format!("Duplicate field '{}' in record '{}'", field, name)));
+            }
+            self.advance();
+            let default = if self.current_token() == &Token::Operator("=".to_string()) {
+                self.advance();
+                Some(self.parse_expression()?)
+            } else {
+                if fields.iter().any(|(_, default)| default.is_some()) {
+                    return Err(self.error(&format!("Field '{}' without a default value follows a field with a default", field)));
+                }
+                None
+            };
+            fields.push((field, default));
+            if self.current_token() == &Token::Delimiter(",".to_string()) {
+                self.advance();
+            }
+        }
+        self.consume(&Token::Delimiter("}".to_string()), "Expected '}' at end of record declaration")?;
+        Ok(Statement::RecordDecl { name, fields, line })
     }
 
-    fn parse_block(&mut self) -> Result<Vec<Statement>, String> {
+    /// Analizza il corpo di un blocco dopo la '{', recuperando dagli errori dei singoli statement.
+    fn parse_block(&mut self) -> Result<Block, ParseError> {
+        self.enter_block();
         let mut body = Vec::new();
         while self.current_token() != &Token::Delimiter("}".to_string()) && self.current_token() != &Token::EOF {
-            body.push(self.parse_statement()?);
+            if let Some(stmt) = self.parse_statement_or_recover() {
+                body.push(stmt);
+            }
         }
+        self.exit_block();
         self.consume(&Token::Delimiter("}".to_string()), "Expected '}' at end of block")?;
-        Ok(body)
+        Ok(body.into())
     }
 
-    fn parse_identifier_statement(&mut self) -> Result<Statement, String> {
+    fn parse_identifier_statement(&mut self) -> Result<Statement, ParseError> {
+        let line = self.current_location().0;
         let lhs = self.parse_expression()?;
 
+        if self.current_token() == &Token::Delimiter(",".to_string()) {
+            return self.parse_multi_assignment(lhs, line);
+        }
         if let Token::Operator(ref op) = self.current_token() {
             match op.as_str() {
                 "=" => {
                     self.advance();
                     let value = self.parse_expression()?;
-                    Ok(Statement::Assignment { target: lhs, value })
+                    Ok(Statement::Assignment { target: lhs, value, line })
                 }
                 "++" | "--" => {
                     let actual_op = if op == "++" { "+" } else { "-" };
@@ -305,8 +495,9 @@ impl Parser {
                         value: Expression::BinaryOp {
                             left: Box::new(lhs),
                             operator: actual_op.to_string(),
-                            right: Box::new(Expression::LiteralNum(1.0)),
+                            right: Box::new(Expression::LiteralInt(1)),
                         },
+                        line,
                     })
                 }
                 "+=" | "-=" | "*=" | "/=" => {
@@ -320,12 +511,36 @@ impl Parser {
                             operator: actual_op,
                             right: Box::new(right),
                         },
+                        line,
                     })
                 }
-                _ => Ok(Statement::Expr(lhs)),
+                _ => Ok(Statement::Expr { expr: lhs, line }),
             }
         } else {
-            Ok(Statement::Expr(lhs))
+            Ok(Statement::Expr { expr: lhs, line })
+        }
+    }
+
+    /// Assegnazione multipla `a, b = b, a`: un valore per destinazione, oppure un solo array da spacchettare.
+    fn parse_multi_assignment(&mut self, first: Expression, line: usize) -> Result<Statement, ParseError> {
+        let mut targets = vec![first];
+        while self.current_token() == &Token::Delimiter(",".to_string()) {
+            self.advance();
+            targets.push(self.parse_expression()?);
+        }
+        self.consume(&Token::Operator("=".to_string()), "Expected '=' after assignment targets")?;
+        let mut values = vec![self.parse_expression()?];
+        while self.current_token() == &Token::Delimiter(",".to_string()) {
+            self.advance();
+            values.push(self.parse_expression()?);
+        }
+        if values.len() != 1 && values.len() != targets.len() {
+            return Err(self.error(&format!(
+                "Multiple assignment has {} targets but {} values",
+                targets.len(),
+                values.len()
+            )));
         }
+        Ok(Statement::MultiAssignment { targets, values, line })
     }
-}
\ No newline at end of file
+}
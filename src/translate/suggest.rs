@@ -0,0 +1,168 @@
+use crate::engine::filter::CONTEXTUAL_KEYWORDS;
+use crate::engine::import::ImportManager;
+use crate::engine::translate::TranslationEngine;
+
+/// Suggerimenti per le parole non riconosciute, basati sul vocabolario di tutte le lingue caricate.
+pub struct Suggestions<'a> {
+    translation: &'a TranslationEngine,
+    import_manager: &'a ImportManager,
+}
+
+impl<'a> Suggestions<'a> {
+    pub fn new(translation: &'a TranslationEngine, import_manager: &'a ImportManager) -> Self {
+        Self { translation, import_manager }
+    }
+
+    /// Suggerimento per una parola usata al posto di una keyword: keyword di una lingua
+    /// non importata oppure keyword attiva scritta in modo simile.
+    pub fn for_keyword(&self, word: &str) -> Option<String> {
+        self.foreign_keyword(word).or_else(|| {
+            let keywords = self.active_spellings(false);
+            closest(self.translation, word, keywords.iter().map(|s| s.as_str()))
+                .map(|keyword| format!("did you mean '{}'?", keyword))
+        })
+    }
+
+    /// Suggerimento per due parole adiacenti al posto di una keyword: spesso è una keyword
+    /// composta scritta con uno spazio (es. "cat timp" per "cattimp"), altrimenti conta la prima.
+    pub fn for_words(&self, first: &str, second: &str) -> Option<String> {
+        let joined = format!("{}{}", first, second);
+        let keywords = self.active_spellings(false);
+        if let Some(keyword) = keywords.iter().find(|k| self.translation.normalize(k) == self.translation.normalize(&joined)) {
+            return Some(format!("did you mean '{}'?", keyword));
+        }
+        self.foreign_keyword(&joined).or_else(|| self.for_keyword(first))
+    }
+
+    /// Suggerimento per un nome non definito: keyword di una lingua non importata, oppure il nome
+    /// più simile tra quelli dichiarati, i built-in importati e le keyword attive.
+    pub fn for_name<'b>(&self, word: &str, declared: impl Iterator<Item = &'b str>) -> Option<String> {
+        self.foreign_keyword(word).or_else(|| {
+            let words = self.active_spellings(true);
+            let mut candidates: Vec<&str> = declared.collect();
+            candidates.extend(words.iter().map(|s| s.as_str()));
+            closest(self.translation, word, candidates.into_iter()).map(|name| format!("did you mean '{}'?", name))
+        })
+    }
+
+    /// Se la parola è una keyword o un built-in di una lingua non importata, spiega come attivarla
+    /// con un import scritto nella lingua principale del programma.
+    fn foreign_keyword(&self, word: &str) -> Option<String> {
+        let candidates = self.translation.keyword_map.get(&self.translation.normalize(word))?;
+        let (canonical, module, language) = candidates.iter().find(|(_, _, language)| {
+            !language.is_empty() && !self.import_manager.is_member_active(language, "translate")
+        })?;
+
+        let primary = self.import_manager.primary_language().unwrap_or("english");
+        let spell = |canonical: &'a str| self.translation.preferred_spelling(primary, canonical).unwrap_or(canonical);
+        let import = format!("{} {} {} translate", spell("import"), spell(language), spell("from"));
+        let kind = if module.is_empty() { "keyword" } else { "built-in" };
+        Some(format!("'{}' is the {} {} '{}', add '{}' to use it", word, language, kind, canonical, import))
+    }
+
+    /// Grafie delle keyword (o dei built-in importati) delle lingue attive.
+    fn active_spellings(&self, include_builtins: bool) -> Vec<String> {
+        let mut spellings = Vec::new();
+        for language in self.translation.languages() {
+            if !self.import_manager.is_member_active(language, "translate") {
+                continue;
+            }
+            for (canonical, words) in self.translation.vocabulary(language) {
+                if canonical == "import" || canonical == "from" || self.translation.is_language(canonical) {
+                    continue;
+                }
+                if CONTEXTUAL_KEYWORDS.contains(&canonical) {
+                    continue;
+                }
+                let module = self.translation.module_of(canonical).unwrap_or("");
+                let is_builtin = !module.is_empty();
+                if is_builtin && !(include_builtins && self.import_manager.is_member_imported(canonical)) {
+                    continue;
+                }
+                spellings.extend(words.iter().cloned());
+            }
+        }
+        spellings
+    }
+}
+
+/// Restituisce il candidato più simile alla parola, se abbastanza vicino da essere un probabile refuso.
+/// Il confronto avviene sulle forme normalizzate; a parità di distanza vince il primo in ordine alfabetico.
+fn closest<'b>(translation: &TranslationEngine, word: &str, candidates: impl Iterator<Item = &'b str>) -> Option<&'b str> {
+    let normalized = translation.normalize(word);
+    // Le parole molto corte sono simili a troppe altre per dare un suggerimento utile
+    if normalized.chars().count() < 3 {
+        return None;
+    }
+    let limit = (normalized.chars().count() / 3).max(1);
+    candidates
+        .filter(|candidate| *candidate != word)
+        .map(|candidate| (edit_distance(&normalized, &translation.normalize(candidate)), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Distanza di edit (Damerau-Levenshtein ristretta): inserimenti, cancellazioni, sostituzioni
+/// e scambi di due caratteri adiacenti costano 1.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut dist = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dist[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            dist[i][j] = (dist[i - 1][j] + 1).min(dist[i][j - 1] + 1).min(dist[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                dist[i][j] = dist[i][j].min(dist[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    dist[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("funzione", "funzione"), 0);
+        assert_eq!(edit_distance("funzoine", "funzione"), 1);
+        assert_eq!(edit_distance("stmapa", "stampa"), 1);
+        assert_eq!(edit_distance("contatre", "contatore"), 1);
+        assert_eq!(edit_distance("mentre", "mientras"), 3);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let translation = TranslationEngine::new();
+        let mut import_manager = ImportManager::new();
+        import_manager.import_member("italian", "translate");
+        import_manager.import_member("print", "nio");
+        let suggestions = Suggestions::new(&translation, &import_manager);
+
+        // Keyword di una lingua non importata, con l'import scritto nella lingua principale
+        assert_eq!(
+            suggestions.for_keyword("mientras").as_deref(),
+            Some("'mientras' is the spanish keyword 'while', add 'importa spagnolo da translate' to use it")
+        );
+        // Keyword e built-in attivi scritti male
+        assert_eq!(suggestions.for_keyword("funzoine").as_deref(), Some("did you mean 'funzione'?"));
+        assert_eq!(suggestions.for_words("altri", "menti").as_deref(), Some("did you mean 'altrimenti'?"));
+        assert_eq!(suggestions.for_words("funzoine", "f").as_deref(), Some("did you mean 'funzione'?"));
+        assert_eq!(suggestions.for_name("stmapa", std::iter::empty()).as_deref(), Some("did you mean 'stampa'?"));
+        // Nomi dichiarati dall'utente
+        let declared = ["contatore", "totale"];
+        assert_eq!(suggestions.for_name("contatre", declared.into_iter()).as_deref(), Some("did you mean 'contatore'?"));
+        assert_eq!(suggestions.for_name("xyz", declared.into_iter()), None);
+        // I built-in non importati non vengono suggeriti
+        assert_eq!(suggestions.for_name("inserisc", std::iter::empty()), None);
+    }
+}
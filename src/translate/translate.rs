@@ -1,8 +1,54 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use icu_normalizer::DecomposingNormalizerBorrowed;
+use icu_properties::CodePointMapData;
+use icu_properties::props::{GeneralCategory, GeneralCategoryGroup};
+use crate::engine::value::Locale;
+
+/// Parola che due lingue diverse traducono in keyword canoniche diverse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordConflict {
+    pub word: String,
+    pub first_language: String,
+    pub first_keyword: String,
+    pub second_language: String,
+    pub second_keyword: String,
+}
+
+/// Lingue incluse nel compilatore: codice del file e nome canonico.
+const BUILTIN_LANGUAGES: [(&str, &str); 10] = [
+    ("en", "english"),
+    ("it", "italian"),
+    ("es", "spanish"),
+    ("fr", "french"),
+    ("de", "german"),
+    ("pt", "portuguese"),
+    ("ro", "romanian"),
+    ("el", "greek"),
+    ("ru", "russian"),
+    ("ar", "arabic"),
+];
+
+/// Lingue che scrivono i numeri decimali con la virgola; le altre (e i language pack) usano il punto.
+const COMMA_DECIMAL_LANGUAGES: [&str; 8] = [
+    "italian", "spanish", "french", "german", "portuguese", "romanian", "greek", "russian",
+];
+
+/// Voci di un file di lingua: keyword canonica -> (grafie accettate, modulo).
+/// La prima grafia è quella preferita.
+type LanguageEntries = HashMap<String, (Vec<String>, String)>;
 
 pub struct TranslationEngine {
     pub(crate) keyword_map: HashMap<String, Vec<(String, String, String)>>,
     module_map: HashMap<String, String>,
+    // Grafie di ogni keyword per (lingua, keyword canonica), a partire da quella preferita
+    spellings: HashMap<(String, String), Vec<String>>,
+    // Nomi canonici delle lingue caricate (incluse e language pack), in ordine di caricamento
+    languages: Vec<String>,
+    // Language pack trovati ma non ancora importati: vengono validati solo al primo import
+    packs: HashMap<String, PathBuf>,
+    // Avvisi prodotti dai pack importati (keyword mancanti sostituite dall'inglese)
+    pack_warnings: Vec<String>,
 }
 
 impl TranslationEngine {
@@ -11,14 +57,14 @@ impl TranslationEngine {
         let mut engine = Self {
             keyword_map: HashMap::new(),
             module_map: HashMap::new(),
+            spellings: HashMap::new(),
+            languages: BUILTIN_LANGUAGES.iter().map(|(_, name)| name.to_string()).collect(),
+            packs: HashMap::new(),
+            pack_warnings: Vec::new(),
         };
-        engine.load_language("en");
-        engine.load_language("it");
-        engine.load_language("es");
-        engine.load_language("fr");
-        engine.load_language("de");
-        engine.load_language("pt");
-        engine.load_language("ro");
+        for (code, _) in BUILTIN_LANGUAGES {
+            engine.load_language(code);
+        }
         engine
     }
 
@@ -31,6 +77,9 @@ impl TranslationEngine {
             "de" => include_str!("languages/de.json"),
             "pt" => include_str!("languages/pt.json"),
             "ro" => include_str!("languages/ro.json"),
+            "el" => include_str!("languages/el.json"),
+            "ru" => include_str!("languages/ru.json"),
+            "ar" => include_str!("languages/ar.json"),
             _ => "",
         };
 
@@ -38,68 +87,274 @@ impl TranslationEngine {
             return;
         }
 
-        if let Ok(map) = serde_json::from_str::<HashMap<String, (String, String)>>(json_content) {
-            let lang_name = match lang {
-                "en" => "english",
-                "it" => "italian",
-                "es" => "spanish",
-                "fr" => "french",
-                "de" => "german",
-                "pt" => "portuguese",
-                "ro" => "romanian",
-                other => other,
-            }.to_string();
-
-            for (canonical_kw, (translation, module)) in map {
-                let normalized = self.normalize(&translation);
-                
-                // Le keyword di base e i nomi delle lingue non hanno dipendenze (sempre attivi per l'avvio)
-                let is_bootstrap = canonical_kw == "import" 
-                    || canonical_kw == "from"
-                    || canonical_kw == "english"
-                    || canonical_kw == "italian"
-                    || canonical_kw == "spanish"
-                    || canonical_kw == "french"
-                    || canonical_kw == "german"
-                    || canonical_kw == "portuguese"
-                    || canonical_kw == "romanian";
-
-                let dep_lang = if is_bootstrap {
-                    "".to_string()
-                } else {
-                    lang_name.clone()
-                };
-
-                let candidates = self.keyword_map.entry(normalized).or_insert_with(Vec::new);
+        if let Ok(map) = parse_language(json_content) {
+            let lang_name = BUILTIN_LANGUAGES
+                .iter()
+                .find(|(code, _)| *code == lang)
+                .map_or(lang, |(_, name)| name)
+                .to_string();
+            self.register_translations(&lang_name, map);
+        }
+    }
+
+    /// Registra le traduzioni di una lingua nel vocabolario.
+    fn register_translations(&mut self, lang_name: &str, map: LanguageEntries) {
+        for (canonical_kw, (spellings, module)) in map {
+            // Le keyword di base e i nomi delle lingue non hanno dipendenze (sempre attivi per l'avvio)
+            let is_bootstrap = canonical_kw == "import"
+                || canonical_kw == "from"
+                || self.is_language(&canonical_kw);
+
+            let dep_lang = if is_bootstrap {
+                "".to_string()
+            } else {
+                lang_name.to_string()
+            };
+
+            for spelling in &spellings {
+                let normalized = self.normalize(spelling);
+                let candidates = self.keyword_map.entry(normalized).or_default();
                 if !candidates.iter().any(|(c, m, l)| c == &canonical_kw && m == &module && l == &dep_lang) {
-                    candidates.push((canonical_kw.clone(), module.clone(), dep_lang));
+                    candidates.push((canonical_kw.clone(), module.clone(), dep_lang.clone()));
                 }
+            }
 
-                self.module_map.insert(canonical_kw, module);
+            self.spellings.insert((lang_name.to_string(), canonical_kw.clone()), spellings);
+            self.module_map.insert(canonical_kw, module);
+        }
+    }
+
+    /// Verifica se il nome canonico appartiene ad una lingua caricata.
+    pub fn is_language(&self, name: &str) -> bool {
+        self.languages.iter().any(|l| l == name)
+    }
+
+    /// Nomi canonici delle lingue caricate, in ordine di caricamento.
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Modulo di appartenenza di una keyword canonica ("" per le keyword del linguaggio).
+    pub fn module_of(&self, canonical: &str) -> Option<&str> {
+        self.module_map.get(canonical).map(|m| m.as_str())
+    }
+
+    /// Grafia preferita di una keyword canonica in una lingua.
+    pub fn preferred_spelling(&self, language: &str, canonical: &str) -> Option<&str> {
+        self.spellings
+            .get(&(language.to_string(), canonical.to_string()))
+            .and_then(|spellings| spellings.first())
+            .map(|s| s.as_str())
+    }
+
+    /// Formato di visualizzazione dei valori per una lingua: grafie preferite di true, false e null
+    /// e separatore decimale della lingua.
+    pub fn locale(&self, language: &str) -> Locale {
+        let neutral = Locale::default();
+        let word = |canonical: &str, fallback: String| {
+            self.preferred_spelling(language, canonical).map(|s| s.to_string()).unwrap_or(fallback)
+        };
+        Locale {
+            true_word: word("true", neutral.true_word),
+            false_word: word("false", neutral.false_word),
+            null_word: word("null", neutral.null_word),
+            decimal_separator: if COMMA_DECIMAL_LANGUAGES.contains(&language) { ',' } else { '.' },
+            collate: neutral.collate,
+        }
+    }
+
+    /// Grafie normalizzate delle keyword indicate nelle lingue importate, associate alla forma canonica.
+    /// Serve al parser per riconoscere le keyword contestuali scritte come identificatori.
+    pub fn active_spellings(&self, canonicals: &[&str], import_manager: &crate::engine::import::ImportManager) -> HashMap<String, String> {
+        let mut spellings = HashMap::new();
+        for language in &self.languages {
+            if !import_manager.is_member_active(language, "translate") {
+                continue;
+            }
+            for canonical in canonicals {
+                for spelling in self.spellings.get(&(language.clone(), canonical.to_string())).into_iter().flatten() {
+                    spellings.entry(self.normalize(spelling)).or_insert_with(|| canonical.to_string());
+                }
             }
         }
+        spellings
     }
 
-    /// Normalizza una stringa in minuscolo e rimuove gli accenti.
-    pub fn normalize(&self, text: &str) -> String {
-        let mut normalized = String::new();
-        for c in text.to_lowercase().chars() {
-            let stripped = match c {
-                'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'æ' | 'ă' => 'a',
-                'è' | 'é' | 'ê' | 'ë' => 'e',
-                'ì' | 'í' | 'î' | 'ï' => 'i',
-                'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
-                'ù' | 'ú' | 'û' | 'ü' => 'u',
-                'ç' => 'c',
-                'ñ' => 'n',
-                'ß' => 's',
-                'ș' | 'ş' => 's',
-                'ț' | 'ţ' => 't',
-                other => other,
-            };
-            normalized.push(stripped);
+    /// Keyword canoniche di una lingua con le grafie accettate, in ordine alfabetico.
+    /// La prima grafia di ogni keyword è quella preferita, da usare nei testi generati.
+    pub fn vocabulary(&self, language: &str) -> Vec<(&str, &[String])> {
+        let mut entries: Vec<(&str, &[String])> = self
+            .spellings
+            .iter()
+            .filter(|((lang, _), _)| lang == language)
+            .map(|((_, canonical), spellings)| (canonical.as_str(), spellings.as_slice()))
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    /// Registra un language pack da un file JSON nello stesso formato delle lingue incluse.
+    /// Il nome canonico della lingua è il nome del file (es. "catalan.json" -> "catalan").
+    /// Qui vengono attivati solo il nome della lingua e le parole dell'import (es. "importa català de translate"):
+    /// il resto del pack viene validato da `activate_pack` quando un programma lo importa,
+    /// così che un pack non valido non blocchi i programmi che non lo usano.
+    pub fn load_pack(&mut self, path: &Path) -> Result<String, Vec<String>> {
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let pack_error = |msg: String| format!("Language Pack Error ({}): {}", file_name, msg);
+
+        let lang_name = path.file_stem().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+        if lang_name.is_empty() || !lang_name.chars().all(|c| c.is_alphabetic()) {
+            return Err(vec![pack_error(format!(
+                "Invalid language name '{}': the file name must contain only letters",
+                lang_name
+            ))]);
+        }
+        if self.is_language(&lang_name) {
+            return Err(vec![pack_error(format!("Language '{}' is already loaded", lang_name))]);
+        }
+
+        let json_content = std::fs::read_to_string(path)
+            .map_err(|e| vec![pack_error(format!("Could not read file: {}", e))])?;
+
+        self.languages.push(lang_name.clone());
+        self.packs.insert(lang_name.clone(), path.to_path_buf());
+        // Un pack illeggibile resta importabile con il nome del file: l'errore di formato emerge all'import
+        if let Ok(mut map) = parse_language(&json_content) {
+            map.retain(|key, _| key == "import" || key == "from" || self.is_language(key));
+            self.register_translations(&lang_name, map);
+        }
+        Ok(lang_name)
+    }
+
+    /// Valida e registra il vocabolario completo di un language pack importato.
+    /// Le keyword mancanti producono un avviso (vedi `take_pack_warnings`) e usano la grafia inglese;
+    /// gli altri problemi (chiavi duplicate, underscore, traduzioni ripetute, ...) sono errori.
+    /// Non fa nulla per le lingue incluse e per i pack già attivati.
+    pub fn activate_pack(&mut self, lang_name: &str) -> Result<(), Vec<String>> {
+        let Some(path) = self.packs.remove(lang_name) else {
+            return Ok(());
+        };
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let pack_error = |msg: String| format!("Language Pack Error ({}): {}", file_name, msg);
+
+        let json_content = std::fs::read_to_string(&path)
+            .map_err(|e| vec![pack_error(format!("Could not read file: {}", e))])?;
+
+        let mut errors = Vec::new();
+        let mut seen_keys = HashSet::new();
+        for key in top_level_keys(&json_content) {
+            if !seen_keys.insert(key.clone()) {
+                errors.push(pack_error(format!("Duplicate key '{}'", key)));
+            }
+        }
+
+        let mut map = parse_language(&json_content).map_err(|e| {
+            vec![pack_error(format!(
+                "Invalid format, expected {{\"keyword\": [\"translation\", \"module\"]}} or {{\"keyword\": [[\"preferred\", \"synonym\"], \"module\"]}}: {}",
+                e
+            ))]
+        })?;
+
+        // L'inglese è il riferimento per le keyword canoniche e per i moduli di appartenenza
+        let reference: HashMap<String, (String, String)> =
+            serde_json::from_str(include_str!("languages/en.json")).unwrap_or_default();
+        let mut required: Vec<&String> = reference.keys().filter(|k| !self.is_language(k)).collect();
+        required.sort();
+        for key in required {
+            if !map.contains_key(key) {
+                let (spelling, module) = &reference[key];
+                self.pack_warnings.push(format!(
+                    "Language Pack Warning ({}): Missing canonical key '{}', using the English '{}'",
+                    file_name, key, spelling
+                ));
+                map.insert(key.clone(), (vec![spelling.clone()], module.clone()));
+            }
+        }
+
+        let mut entries: Vec<(&String, &(Vec<String>, String))> = map.iter().collect();
+        entries.sort();
+        let mut translations: HashMap<String, &String> = HashMap::new();
+        for (key, (spellings, module)) in entries {
+            let is_language_name = self.is_language(key);
+            match reference.get(key) {
+                Some((_, expected_module)) if module != expected_module => {
+                    errors.push(pack_error(format!("Key '{}' must belong to module '{}'", key, expected_module)));
+                }
+                None if !is_language_name => {
+                    errors.push(pack_error(format!("Unknown canonical key '{}'", key)));
+                }
+                _ => {}
+            }
+            // La regola dell'underscore vale per la traduzione ufficiale, non per i sinonimi
+            if spellings[0].contains('_') {
+                errors.push(pack_error(format!("Translation '{}' of '{}' contains an underscore", spellings[0], key)));
+            }
+            for translation in spellings {
+                if translation.trim().is_empty() || translation.chars().any(char::is_whitespace) {
+                    errors.push(pack_error(format!("Translation of '{}' must be a single non-empty word", key)));
+                }
+                // Due keyword con la stessa traduzione sarebbero indistinguibili nel sorgente
+                if !is_language_name {
+                    match translations.insert(self.normalize(translation), key) {
+                        Some(previous) if previous == key => {
+                            errors.push(pack_error(format!("Translation '{}' is repeated for '{}'", translation, key)));
+                        }
+                        Some(previous) => {
+                            errors.push(pack_error(format!(
+                                "Translation '{}' is used for both '{}' and '{}'",
+                                translation, previous, key
+                            )));
+                        }
+                        None => {}
+                    }
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.register_translations(lang_name, map);
+        Ok(())
+    }
+
+    /// Restituisce e azzera gli avvisi dei language pack attivati finora.
+    pub fn take_pack_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pack_warnings)
+    }
+
+    /// Registra tutti i language pack (*.json) di una cartella, in ordine alfabetico.
+    /// Restituisce gli errori dei pack che non è stato possibile registrare.
+    pub fn load_pack_dir(&mut self, dir: &Path) -> Vec<String> {
+        let mut paths: Vec<PathBuf> = match std::fs::read_dir(dir) {
+            Ok(entries) => entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect(),
+            Err(e) => return vec![format!("Language Pack Error ({}): Could not read directory: {}", dir.display(), e)],
+        };
+        paths.sort();
+
+        let mut errors = Vec::new();
+        for path in paths {
+            if let Err(mut pack_errors) = self.load_pack(&path) {
+                errors.append(&mut pack_errors);
+            }
         }
-        normalized
+        errors
+    }
+
+    /// Cartella predefinita dei language pack dell'utente: ~/.nodestract/languages
+    pub fn default_pack_dir() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".nodestract").join("languages"))
+    }
+
+    /// Normalizza una parola per il confronto tra keyword: decomposizione canonica (NFD),
+    /// rimozione dei segni combinanti (accenti latini, tonos greco, harakat arabe) e case folding.
+    pub fn normalize(&self, text: &str) -> String {
+        fold(text)
     }
 
     /// Risolve una keyword localizzata nella forma inglese canonica se il rispettivo modulo è importato.
@@ -109,8 +364,8 @@ impl TranslationEngine {
             for (canonical, module, language) in candidates {
                 let lang_active = language.is_empty() || import_manager.is_member_active(language, "translate");
                 if lang_active {
-                    if module == "nio" || module == "nmath" || module == "nfs" || module == "nnet" {
-                        if import_manager.is_member_active(canonical, module) {
+                    if is_builtin_module(module) {
+                        if import_manager.is_member_imported(canonical) {
                             return Some(canonical.as_str());
                         }
                     } else {
@@ -129,7 +384,9 @@ impl TranslationEngine {
             for (canonical, module, language) in candidates {
                 let lang_active = language.is_empty() || import_manager.is_member_active(language, "translate");
                 if lang_active {
-                    let matches_parent = module == parent || (parent == "translate" && module.is_empty());
+                    let matches_parent = module == parent
+                        || (parent == "translate" && module.is_empty())
+                        || (is_builtin_module(module) && import_manager.is_member_of(canonical, parent));
                     if matches_parent {
                         return Some(canonical.as_str());
                     }
@@ -141,12 +398,43 @@ impl TranslationEngine {
 
 
 
+    /// Elenca tutte le collisioni tra lingue diverse, ordinate per parola e coppia di lingue.
+    /// Le keyword di bootstrap e le traduzioni identiche (es. "in") non sono collisioni.
+    pub fn find_conflicts(&self) -> Vec<KeywordConflict> {
+        let mut conflicts = Vec::new();
+        for (word, candidates) in &self.keyword_map {
+            for (i, (first_kw, _, first_lang)) in candidates.iter().enumerate() {
+                for (second_kw, _, second_lang) in &candidates[i + 1..] {
+                    if first_lang.is_empty() || second_lang.is_empty() || first_lang == second_lang || first_kw == second_kw {
+                        continue;
+                    }
+                    let (first, second) = if first_lang < second_lang {
+                        ((first_lang, first_kw), (second_lang, second_kw))
+                    } else {
+                        ((second_lang, second_kw), (first_lang, first_kw))
+                    };
+                    conflicts.push(KeywordConflict {
+                        word: word.clone(),
+                        first_language: first.0.clone(),
+                        first_keyword: first.1.clone(),
+                        second_language: second.0.clone(),
+                        second_keyword: second.1.clone(),
+                    });
+                }
+            }
+        }
+        conflicts.sort_by(|a, b| {
+            (&a.word, &a.first_language, &a.second_language).cmp(&(&b.word, &b.first_language, &b.second_language))
+        });
+        conflicts
+    }
+
     /// Controlla se un identificatore corrisponde a una funzione built-in conosciuta.
     pub fn get_builtin_info(&self, word: &str) -> Option<(&str, &str)> {
         let normalized = self.normalize(word);
         if let Some(candidates) = self.keyword_map.get(&normalized) {
             for (canonical, module, _language) in candidates {
-                if module == "nio" || module == "nmath" || module == "nfs" || module == "nnet" {
+                if is_builtin_module(module) {
                     return Some((canonical.as_str(), module.as_str()));
                 }
             }
@@ -155,10 +443,109 @@ impl TranslationEngine {
     }
 }
 
+/// Moduli di built-in importabili: le loro parole sono keyword solo dopo l'import.
+const BUILTIN_MODULES: [&str; 7] = ["nio", "nmath", "nfs", "nnet", "nconc", "niter", "ndata"];
+
+/// Verifica se il modulo di una voce è un modulo di built-in ("" indica le keyword del linguaggio).
+pub fn is_builtin_module(module: &str) -> bool {
+    BUILTIN_MODULES.contains(&module)
+}
+
+/// Legge un file di lingua. Ogni voce è `[traduzione, modulo]` oppure, per accettare più grafie,
+/// `[[preferita, sinonimo, ...], modulo]`.
+fn parse_language(json: &str) -> Result<LanguageEntries, String> {
+    let raw: HashMap<String, (serde_json::Value, String)> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let mut entries = HashMap::new();
+    for (key, (value, module)) in raw {
+        let spellings = match value {
+            serde_json::Value::String(spelling) => vec![spelling],
+            serde_json::Value::Array(items) => items
+                .into_iter()
+                .map(|item| match item {
+                    serde_json::Value::String(spelling) => Ok(spelling),
+                    other => Err(format!("spelling {} of '{}' is not a string", other, key)),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("translation {} of '{}' is not a string or a list", other, key)),
+        };
+        if spellings.is_empty() {
+            return Err(format!("'{}' has no translation", key));
+        }
+        entries.insert(key, (spellings, module));
+    }
+    Ok(entries)
+}
+
+/// Estrae le chiavi di primo livello di un oggetto JSON nell'ordine in cui compaiono.
+/// Serve a rilevare le chiavi duplicate, che la deserializzazione in una mappa scarterebbe.
+fn top_level_keys(json: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut depth = 0;
+    let mut chars = json.chars().peekable();
+    let mut expecting_key = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' => {
+                depth += 1;
+                expecting_key = c == '{' && depth == 1;
+            }
+            '}' | ']' => depth -= 1,
+            ',' if depth == 1 => expecting_key = true,
+            '"' => {
+                let mut text = String::new();
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                text.push(escaped);
+                            }
+                        }
+                        '"' => break,
+                        other => text.push(other),
+                    }
+                }
+                if depth == 1 && expecting_key {
+                    keys.push(text);
+                    expecting_key = false;
+                }
+            }
+            _ => {}
+        }
+    }
+    keys
+}
+
+/// Forma senza accenti e in minuscolo di un testo (NFD, rimozione dei segni combinanti e case folding),
+/// usata per confrontare le keyword e per l'ordinamento delle stringhe secondo la lingua.
+pub fn fold(text: &str) -> String {
+    let decomposed = DecomposingNormalizerBorrowed::new_nfd().normalize(text);
+    let categories = CodePointMapData::<GeneralCategory>::new();
+    let mut normalized = String::new();
+    for c in decomposed.chars() {
+        // Il tatweel arabo (ـ) allunga solo graficamente la parola
+        if GeneralCategoryGroup::Mark.contains(categories.get(c)) || c == '\u{0640}' {
+            continue;
+        }
+        for lower in c.to_lowercase() {
+            match lower {
+                // Forme che il minuscolo semplice non unifica
+                'ς' => normalized.push('σ'),
+                'ß' => normalized.push_str("ss"),
+                // Lettere latine senza decomposizione canonica
+                'æ' => normalized.push('a'),
+                'ø' => normalized.push('o'),
+                other => normalized.push(other),
+            }
+        }
+    }
+    normalized
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::engine::import::ImportManager;
+    use crate::engine::value::Value;
 
     #[test]
     fn test_normalization() {
@@ -166,6 +553,15 @@ mod tests {
         assert_eq!(engine.normalize("SÉ"), "se");
         assert_eq!(engine.normalize("funcția"), "functia");
         assert_eq!(engine.normalize("München"), "munchen");
+        assert_eq!(engine.normalize("Straße"), "strasse");
+        // Greco: tonos rimosso e sigma finale unificata
+        assert_eq!(engine.normalize("ΌΣΟ"), "οσο");
+        assert_eq!(engine.normalize("Λόγος"), "λογοσ");
+        // Cirillico: la dieresi di "ё" è un segno combinante
+        assert_eq!(engine.normalize("ЁЖ"), "еж");
+        // Arabo: hamza, harakat e tatweel non distinguono le parole
+        assert_eq!(engine.normalize("إِذَا"), engine.normalize("اذا"));
+        assert_eq!(engine.normalize("طـالما"), "طالما");
     }
 
     #[test]
@@ -187,4 +583,153 @@ mod tests {
         // Spanish
         assert_eq!(engine.lookup("si", &import_manager), Some("if"));
     }
+
+    #[test]
+    fn test_keyword_synonyms() {
+        let engine = TranslationEngine::new();
+        let mut import_manager = ImportManager::new();
+        import_manager.import_member("italian", "translate");
+        import_manager.import_member("spanish", "translate");
+
+        assert_eq!(engine.lookup("funzione", &import_manager), Some("function"));
+        assert_eq!(engine.lookup("fn", &import_manager), Some("function"));
+        assert_eq!(engine.lookup("mientras", &import_manager), Some("while"));
+        assert_eq!(engine.lookup("mientras_que", &import_manager), Some("while"));
+
+        // La grafia preferita è sempre la prima
+        let vocabulary = engine.vocabulary("italian");
+        let function = vocabulary.iter().find(|(canonical, _)| *canonical == "function").unwrap();
+        assert_eq!(function.1, ["funzione".to_string(), "fn".to_string()]);
+    }
+
+    #[test]
+    fn test_locale() {
+        let engine = TranslationEngine::new();
+        let italian = engine.locale("italian");
+        assert_eq!((italian.true_word.as_str(), italian.null_word.as_str()), ("vero", "nullo"));
+        assert_eq!(Value::Float(2.5).print_text(&italian), "2,5");
+        assert_eq!(Value::Float(2.5).display(&italian), "2.5");
+        let nested = Value::array(vec![Value::Float(1.5), Value::Boolean(false), Value::Null]);
+        assert_eq!(nested.print_text(&italian), "[1.5, falso, nullo]");
+
+        let german = engine.locale("german");
+        assert_eq!(Value::Boolean(true).display(&german), "wahr");
+
+        // L'inglese e le lingue sconosciute usano il formato neutro
+        assert_eq!(engine.locale("english"), Locale::default());
+        assert_eq!(engine.locale("klingon"), Locale::default());
+        assert_eq!(Value::Float(2.5).to_string(), "2.5");
+    }
+
+    #[test]
+    fn test_load_language_pack() {
+        let mut engine = crate::engine::Engine::new();
+        engine.quiet = true;
+        let pack = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/languages/catalan.json");
+        assert_eq!(engine.translation_engine.load_pack(&pack), Ok("catalan".to_string()));
+        assert!(engine.translation_engine.is_language("catalan"));
+
+        // La nuova lingua è importabile da "translate" e le sue keyword sono attive
+        let source = "importa català de translate\nfunció doble(n) {\nretorna n * 2\n}\nconstant x = doble(21)\nsi (x != 42) {\nllança \"errore\"\n}\n";
+        assert!(engine.run(source));
+
+        // Lo stesso pack non può essere caricato due volte
+        let errors = engine.translation_engine.load_pack(&pack).unwrap_err();
+        assert_eq!(errors, vec!["Language Pack Error (catalan.json): Language 'catalan' is already loaded".to_string()]);
+    }
+
+    #[test]
+    fn test_invalid_language_pack() {
+        let dir = std::env::temp_dir().join(format!("ns_packs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let reference: HashMap<String, (String, String)> = serde_json::from_str(include_str!("languages/en.json")).unwrap();
+        let mut entries: Vec<String> = reference
+            .iter()
+            .filter(|(key, _)| !matches!(key.as_str(), "sleep" | "english" | "italian" | "spanish" | "french" | "german" | "portuguese" | "romanian"))
+            .map(|(key, (translation, module))| match key.as_str() {
+                "let" => "\"let\": [\"crea_var\", \"\"]".to_string(),
+                "const" => "\"const\": [\"if\", \"\"]".to_string(),
+                "print" => "\"print\": [\"print\", \"nmath\"]".to_string(),
+                // I sinonimi possono contenere underscore, ma non ripetersi
+                "while" => "\"while\": [[\"while\", \"while_not\", \"While\"], \"\"]".to_string(),
+                _ => format!("\"{}\": [\"{}\", \"{}\"]", key, translation, module),
+            })
+            .collect();
+        entries.push("\"exit\": [\"exit\", \"\"]".to_string());
+        entries.push("\"klass\": [\"klass\", \"\"]".to_string());
+        let path = dir.join("dutch.json");
+        std::fs::write(&path, format!("{{{}}}", entries.join(",\n"))).unwrap();
+
+        let mut engine = TranslationEngine::new();
+        // Il pack viene solo registrato: il contenuto è validato quando viene importato
+        assert!(engine.load_pack_dir(&dir).is_empty());
+        assert!(engine.is_language("dutch"));
+        let errors = engine.activate_pack("dutch").unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let expected = [
+            "Duplicate key 'exit'",
+            "Translation 'if' is used for both 'const' and 'if'",
+            "Unknown canonical key 'klass'",
+            "Translation 'crea_var' of 'let' contains an underscore",
+            "Key 'print' must belong to module 'nio'",
+            "Translation 'While' is repeated for 'while'",
+        ];
+        assert_eq!(errors.len(), expected.len(), "{:?}", errors);
+        for (error, expected) in errors.iter().zip(expected) {
+            assert_eq!(error, &format!("Language Pack Error (dutch.json): {}", expected));
+        }
+        // Una keyword mancante non è un errore: viene segnalata e sostituita dalla grafia inglese
+        assert_eq!(
+            engine.take_pack_warnings(),
+            vec!["Language Pack Warning (dutch.json): Missing canonical key 'sleep', using the English 'sleep'".to_string()]
+        );
+        assert_eq!(engine.preferred_spelling("dutch", "let"), None);
+    }
+
+    #[test]
+    fn test_invalid_language_pack_only_fails_when_imported() {
+        let dir = std::env::temp_dir().join(format!("ns_broken_packs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // Pack senza "sleep" e con una keyword sconosciuta
+        let pack = include_str!("../../examples/languages/catalan.json")
+            .replacen("\"sleep\"", "\"klass\"", 1)
+            .replacen("\"catalan\": [\"català\"", "\"broken\": [\"trencat\"", 1);
+        std::fs::write(dir.join("broken.json"), pack).unwrap();
+
+        let mut engine = crate::engine::Engine::new();
+        engine.quiet = true;
+        engine.load_language_packs(&[dir.to_string_lossy().to_string()]);
+
+        // Un programma che non importa il pack non valido non ne risente
+        assert!(engine.run("importa italiano da translate\ncrea x = 1\nse (x != 1) {\nlancia \"errore\"\n}\n"));
+
+        // Importarlo (con le parole dell'import del pack stesso) segnala gli errori del pack
+        let source = "importa trencat de translate\ncrea x = 1";
+        let (_, _, errors) = crate::engine::check::validate_imports(source, &mut engine.translation_engine);
+        assert_eq!(errors, vec!["Language Pack Error (broken.json): Unknown canonical key 'klass'".to_string()]);
+        assert_eq!(
+            engine.translation_engine.take_pack_warnings(),
+            vec!["Language Pack Warning (broken.json): Missing canonical key 'sleep', using the English 'sleep'".to_string()]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let mut engine = TranslationEngine::new();
+        // Le lingue incluse non hanno collisioni tra loro
+        assert!(engine.find_conflicts().is_empty());
+
+        // Simula una lingua in cui "se" significa "in" e una in cui "si" significa ancora "if"
+        engine.keyword_map.entry("se".to_string()).or_default().push(("in".to_string(), "".to_string(), "catalan".to_string()));
+        engine.keyword_map.entry("si".to_string()).or_default().push(("if".to_string(), "".to_string(), "catalan".to_string()));
+
+        // "se" è "if" sia in italiano che in portoghese: due collisioni, in ordine di lingua
+        let conflicts = engine.find_conflicts();
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts.iter().all(|c| c.word == "se" && c.first_language == "catalan" && c.first_keyword == "in"));
+        assert_eq!((conflicts[0].second_language.as_str(), conflicts[0].second_keyword.as_str()), ("italian", "if"));
+        assert_eq!((conflicts[1].second_language.as_str(), conflicts[1].second_keyword.as_str()), ("portuguese", "if"));
+    }
 }
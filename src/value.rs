@@ -1,54 +1,525 @@
-use std::fmt;
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
+use indexmap::IndexMap;
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+use rust_decimal::Decimal;
+use crate::engine::translate::fold;
+use crate::engine::interpreter::iterators::IterRef;
+use crate::engine::interpreter::conc::{Channel, Task};
+
+/// 2^53: sotto questo valore ogni intero è rappresentato esattamente da un f64.
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Null,
     Boolean(bool),
     Integer(i64),
+    // Intero oltre i limiti di i64, prodotto automaticamente in caso di overflow
+    BigInt(BigInt),
     Float(f64),
+    // Decimale esatto, creato esplicitamente con la funzione `decimal`
+    Decimal(Decimal),
     String(String),
-    Array(Vec<Value>),
-    Map(HashMap<String, Value>),
+    // Array e mappe sono riferimenti condivisi: l'assegnazione e il passaggio come argomento non li copiano
+    Array(Rc<RefCell<Vec<Value>>>),
+    // Le chiavi mantengono l'ordine di inserimento (stampa, scrittura JSON)
+    Map(Rc<RefCell<IndexMap<String, Value>>>),
+    // Insieme immutabile di elementi distinti (secondo `equals`), nell'ordine di inserimento
+    Set(Rc<Vec<Value>>),
+    // Istanza di un record dichiarato: campi fissi, modificabili ma non estendibili
+    Record(Rc<RefCell<Record>>),
+    // Iteratore pigro (generatori, adattatori, righe di un file): condiviso come array e mappe
+    Iterator(IterRef),
+    // Canale e task del modulo nconc: gli unici valori condivisi (non copiati) tra i task
+    Channel(Arc<Channel>),
+    Task(Arc<Task>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub fields: IndexMap<String, Value>,
+}
+
+impl Record {
+    /// Errore per l'accesso a un campo non dichiarato (es. `p.nmoe`).
+    pub fn unknown_field(&self, field: &str) -> String {
+        format!("TYPE ERROR: Record '{}' has no field '{}'", self.name, field)
+    }
+}
+
+/// Parole e separatore decimale con cui i valori vengono mostrati nella lingua del programma.
+/// Il separatore vale solo per i numeri stampati da soli (vedi `Value::print_text`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    pub true_word: String,
+    pub false_word: String,
+    pub null_word: String,
+    pub decimal_separator: char,
+    // Ordina le stringhe ignorando maiuscole e accenti (es. "èra" tra "era" e "erba")
+    pub collate: bool,
+}
+
+impl Default for Locale {
+    /// Formato neutro (inglese, punto decimale), usato anche per l'output destinato alle macchine.
+    fn default() -> Self {
+        Self {
+            true_word: "true".to_string(),
+            false_word: "false".to_string(),
+            null_word: "null".to_string(),
+            decimal_separator: '.',
+            collate: false,
+        }
+    }
 }
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.display(&Locale::default()))
+    }
+}
+
+impl Value {
+    pub fn array(items: Vec<Value>) -> Value {
+        Value::Array(Rc::new(RefCell::new(items)))
+    }
+
+    pub fn map(entries: IndexMap<String, Value>) -> Value {
+        Value::Map(Rc::new(RefCell::new(entries)))
+    }
+
+    pub fn record(name: String, fields: IndexMap<String, Value>) -> Value {
+        Value::Record(Rc::new(RefCell::new(Record { name, fields })))
+    }
+
+    /// Insieme con gli elementi distinti di `items`, nell'ordine della prima occorrenza.
+    /// Array e mappe vengono copiati, così che modificarli in seguito non alteri l'insieme.
+    pub fn set(items: Vec<Value>) -> Result<Value, String> {
+        let mut unique: Vec<Value> = Vec::with_capacity(items.len());
+        let mut index = SetIndex::with_capacity(items.len());
+        for item in items {
+            let hash = item.set_hash();
+            if !index.candidates(hash).any(|idx| unique[idx].equals(&item)) {
+                index.push(hash);
+                unique.push(item.deep_copy()?);
+            }
+        }
+        Ok(Value::Set(Rc::new(unique)))
+    }
+
+    /// Impronta coerente con `equals` (valori uguali hanno la stessa impronta), con cui gli insiemi
+    /// evitano di confrontare tutte le coppie di elementi. Le collezioni contribuiscono solo con tipo
+    /// e lunghezza, così il calcolo non entra nelle strutture che contengono se stesse.
+    pub fn set_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
         match self {
-            Value::Null => write!(f, "null"),
-            Value::Boolean(b) => write!(f, "{}", b),
-            Value::Integer(i) => write!(f, "{}", i),
-            Value::Float(n) => write!(f, "{}", n),
-            Value::String(s) => write!(f, "{}", s),
+            Value::Null => 0.hash(&mut hasher),
+            Value::Boolean(b) => (1, b).hash(&mut hasher),
+            Value::String(text) => (2, text).hash(&mut hasher),
+            // Interi, float e decimali uguali hanno lo stesso valore decimale esatto, e quelli interi
+            // la stessa impronta di un i64; oltre 2^53 un float è uguale a più interi diversi,
+            // quindi quei numeri condividono un'unica impronta
+            Value::Integer(i) if i.unsigned_abs() < MAX_EXACT_INTEGER => (3, Some(*i)).hash(&mut hasher),
+            Value::Integer(_) | Value::BigInt(_) | Value::Float(_) | Value::Decimal(_) => {
+                let exact = self.as_f64().filter(|f| f.abs() < MAX_EXACT_INTEGER as f64).and_then(|_| self.as_decimal());
+                match exact {
+                    Some(d) if d.fract().is_zero() => (3, d.to_i64()).hash(&mut hasher),
+                    other => (3, other).hash(&mut hasher),
+                }
+            }
+            Value::Array(items) => (4, items.borrow().len()).hash(&mut hasher),
+            Value::Map(map) => (5, map.borrow().len()).hash(&mut hasher),
+            Value::Set(items) => (6, items.len()).hash(&mut hasher),
+            Value::Record(record) => (7, &record.borrow().name).hash(&mut hasher),
+            Value::Iterator(_) | Value::Channel(_) | Value::Task(_) => 8.hash(&mut hasher),
+        }
+        hasher.finish()
+    }
+
+    /// Converte il valore in testo con le parole della lingua indicata (stampa e concatenazione di stringhe).
+    /// I numeri usano sempre il punto decimale: il testo ottenuto non dipende da `--machine-output`.
+    pub fn display(&self, locale: &Locale) -> String {
+        self.display_nested(locale, &mut Vec::new())
+    }
+
+    /// Testo scritto da `print`: un numero decimale stampato da solo usa il separatore della lingua,
+    /// mentre dentro una collezione resta il punto (`[1.5, 2]` e non l'ambiguo `[1,5, 2]`).
+    pub fn print_text(&self, locale: &Locale) -> String {
+        match self {
+            Value::Float(_) | Value::Decimal(_) => self.display(locale).replace('.', &locale.decimal_separator.to_string()),
+            _ => self.display(locale),
+        }
+    }
+
+    /// `open` contiene gli array e le mappe in corso di stampa: un riferimento a uno di essi
+    /// (struttura che contiene se stessa) viene scritto come `[...]` o `{...}`.
+    fn display_nested(&self, locale: &Locale, open: &mut Vec<*const ()>) -> String {
+        if let Some(ptr) = self.identity() {
+            if open.contains(&ptr) {
+                return match self {
+                    Value::Array(_) => "[...]".to_string(),
+                    Value::Record(record) => format!("{} {{...}}", record.borrow().name),
+                    _ => "{...}".to_string(),
+                };
+            }
+            open.push(ptr);
+        }
+        let text = match self {
+            Value::Null => locale.null_word.clone(),
+            Value::Boolean(true) => locale.true_word.clone(),
+            Value::Boolean(false) => locale.false_word.clone(),
+            Value::Integer(i) => i.to_string(),
+            Value::BigInt(i) => i.to_string(),
+            Value::Float(n) => n.to_string(),
+            Value::Decimal(d) => d.to_string(),
+            Value::String(s) => s.clone(),
             Value::Array(arr) => {
-                let elements: Vec<String> = arr.iter().map(|v| v.to_string()).collect();
-                write!(f, "[{}]", elements.join(", "))
+                let elements: Vec<String> = arr.borrow().iter().map(|v| v.display_nested(locale, open)).collect();
+                format!("[{}]", elements.join(", "))
             }
             Value::Map(map) => {
-                let mut entries: Vec<(&String, &Value)> = map.iter().collect();
-                entries.sort_by_key(|(k, _)| k.as_str());
-                let elements: Vec<String> = entries
+                let elements: Vec<String> = map
+                    .borrow()
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", k, v.display_nested(locale, open)))
+                    .collect();
+                format!("{{{}}}", elements.join(", "))
+            }
+            Value::Set(items) => {
+                let elements: Vec<String> = items.iter().map(|v| v.display_nested(locale, open)).collect();
+                format!("{{{}}}", elements.join(", "))
+            }
+            Value::Iterator(_) => "<iterator>".to_string(),
+            Value::Channel(_) => "<channel>".to_string(),
+            Value::Task(_) => "<task>".to_string(),
+            Value::Record(record) => {
+                let record = record.borrow();
+                let elements: Vec<String> = record
+                    .fields
                     .iter()
-                    .map(|(k, v)| format!("{}: {}", k, v))
+                    .map(|(k, v)| format!("{}: {}", k, v.display_nested(locale, open)))
                     .collect();
-                write!(f, "{{{}}}", elements.join(", "))
+                format!("{} {{{}}}", record.name, elements.join(", "))
             }
+        };
+        if self.identity().is_some() {
+            open.pop();
+        }
+        text
+    }
+
+    /// Indirizzo dei dati condivisi di array e mappe, `None` per i valori semplici.
+    pub(crate) fn identity(&self) -> Option<*const ()> {
+        match self {
+            Value::Array(arr) => Some(Rc::as_ptr(arr) as *const ()),
+            Value::Map(map) => Some(Rc::as_ptr(map) as *const ()),
+            Value::Set(items) => Some(Rc::as_ptr(items) as *const ()),
+            Value::Record(record) => Some(Rc::as_ptr(record) as *const ()),
+            Value::Iterator(iter) => Some(Rc::as_ptr(iter) as *const ()),
+            Value::Channel(channel) => Some(Arc::as_ptr(channel) as *const ()),
+            Value::Task(task) => Some(Arc::as_ptr(task) as *const ()),
+            _ => None,
+        }
+    }
+
+    /// Identità (operatore `is`): array, mappe, insiemi e record sono identici solo se sono lo stesso oggetto,
+    /// i valori semplici se hanno lo stesso tipo e lo stesso valore (`1 is 1.0` è falso).
+    pub fn is_same(&self, other: &Value) -> bool {
+        match (self.identity(), other.identity()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.type_name() == other.type_name() && self.equals(other),
+            _ => false,
+        }
+    }
+
+    /// Copia indipendente, anche degli array e delle mappe annidati (funzione `copy`).
+    /// Una struttura che contiene se stessa non ha una copia finita: è un errore.
+    pub fn deep_copy(&self) -> Result<Value, String> {
+        self.copy_nested(&mut Vec::new())
+    }
+
+    /// `open` contiene le strutture in corso di copia, come in `display_nested`.
+    fn copy_nested(&self, open: &mut Vec<*const ()>) -> Result<Value, String> {
+        let Some(ptr) = self.identity() else {
+            return Ok(self.clone());
+        };
+        if open.contains(&ptr) {
+            return Err(format!("TYPE ERROR: Cannot copy a cyclic value ({} containing itself)", self.type_name()));
+        }
+        open.push(ptr);
+        let copy = match self {
+            Value::Array(arr) => {
+                let items = arr.borrow().iter().map(|v| v.copy_nested(open)).collect::<Result<_, _>>();
+                items.map(Value::array)
+            }
+            Value::Map(map) => {
+                let entries = map.borrow().iter().map(|(k, v)| Ok((k.clone(), v.copy_nested(open)?))).collect::<Result<_, String>>();
+                entries.map(Value::map)
+            }
+            Value::Set(items) => {
+                let items = items.iter().map(|v| v.copy_nested(open)).collect::<Result<_, _>>();
+                items.map(|items| Value::Set(Rc::new(items)))
+            }
+            Value::Record(record) => {
+                let record = record.borrow();
+                let fields = record.fields.iter().map(|(k, v)| Ok((k.clone(), v.copy_nested(open)?))).collect::<Result<_, String>>();
+                fields.map(|fields| Value::record(record.name.clone(), fields))
+            }
+            other => Ok(other.clone()),
+        };
+        open.pop();
+        copy
+    }
+
+    /// Appartenenza (operatore `in`): elemento di un insieme o di un array, chiave di una mappa,
+    /// sottostringa di una stringa.
+    pub fn contains(&self, item: &Value) -> Result<bool, String> {
+        match (self, item) {
+            (Value::Set(items), _) => Ok(items.iter().any(|v| v.equals(item))),
+            (Value::Array(items), _) => Ok(items.borrow().iter().any(|v| v.equals(item))),
+            (Value::Map(map), _) => Ok(map.borrow().contains_key(&item.to_string())),
+            (Value::String(text), Value::String(part)) => Ok(text.contains(part.as_str())),
+            _ => Err(format!("TYPE ERROR: Cannot look for {} in {}", item.type_name(), self.type_name())),
+        }
+    }
+
+    /// Valori visitati da `for ... in`: elementi di array e insiemi, chiavi delle mappe, caratteri delle stringhe.
+    pub fn elements(&self) -> Result<Vec<Value>, String> {
+        match self {
+            Value::Array(items) => Ok(items.borrow().clone()),
+            Value::Set(items) => Ok(items.to_vec()),
+            Value::Map(map) => Ok(map.borrow().keys().map(|k| Value::String(k.clone())).collect()),
+            Value::String(text) => Ok(text.chars().map(|c| Value::String(c.to_string())).collect()),
+            other => Err(format!("TYPE ERROR: Cannot iterate over {}", other.type_name())),
+        }
+    }
+
+    /// Intero di precisione arbitraria, riportato a `Integer` se rientra nei limiti di i64.
+    pub fn from_bigint(value: BigInt) -> Value {
+        match value.to_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::BigInt(value),
+        }
+    }
+
+    /// Valore numerico come f64 (con eventuale perdita di precisione), `None` per i tipi non numerici.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Integer(i) => Some(*i as f64),
+            Value::BigInt(i) => i.to_f64(),
+            Value::Float(f) => Some(*f),
+            Value::Decimal(d) => d.to_f64(),
+            _ => None,
+        }
+    }
+
+    /// Valore numerico come decimale esatto. I float vengono convertiti dalla loro forma più breve
+    /// (es. `0.1` diventa esattamente 0,1); `None` per i tipi non numerici o fuori dall'intervallo dei decimali.
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Value::Integer(i) => Some(Decimal::from(*i)),
+            Value::BigInt(i) => Decimal::from_str(&i.to_string()).ok(),
+            Value::Float(f) => Decimal::from_str(&f.to_string()).ok(),
+            Value::Decimal(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    /// Nome del tipo, usato nei messaggi di errore.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Boolean(_) => "boolean",
+            Value::Integer(_) | Value::BigInt(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Decimal(_) => "decimal",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Map(_) => "map",
+            Value::Set(_) => "set",
+            Value::Record(_) => "record",
+            Value::Iterator(_) => "iterator",
+            Value::Channel(_) => "channel",
+            Value::Task(_) => "task",
+        }
+    }
+
+    /// Uguaglianza strutturale: i numeri si confrontano per valore (`1 == 1.0`), array e mappe
+    /// elemento per elemento, gli insiemi senza tenere conto dell'ordine, i record campo per campo. `null` è uguale solo a `null`;
+    /// valori di tipo diverso sono diversi.
+    pub fn equals(&self, other: &Value) -> bool {
+        self.equals_nested(other, &mut Vec::new())
+    }
+
+    /// `open` contiene le coppie di strutture in corso di confronto: ritrovare la stessa coppia
+    /// (strutture che contengono se stesse) non aggiunge differenze, quindi la coppia è considerata uguale.
+    fn equals_nested(&self, other: &Value, open: &mut Vec<(*const (), *const ())>) -> bool {
+        let pair = match (self.identity(), other.identity()) {
+            (Some(a), Some(b)) if a == b => return true,
+            (Some(a), Some(b)) => {
+                if open.contains(&(a, b)) {
+                    return true;
+                }
+                open.push((a, b));
+                true
+            }
+            _ => false,
+        };
+        let equal = match (self, other) {
+            (Value::Null, Value::Null) => true,
+            (Value::Boolean(a), Value::Boolean(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Array(a), Value::Array(b)) => {
+                let (a, b) = (a.borrow(), b.borrow());
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| x.equals_nested(y, open))
+            }
+            (Value::Map(a), Value::Map(b)) => {
+                let (a, b) = (a.borrow(), b.borrow());
+                a.len() == b.len() && a.iter().all(|(key, x)| b.get(key).is_some_and(|y| x.equals_nested(y, open)))
+            }
+            (Value::Set(a), Value::Set(b)) => {
+                let index = SetIndex::new(b);
+                a.len() == b.len() && a.iter().all(|x| index.candidates(x.set_hash()).any(|idx| x.equals_nested(&b[idx], open)))
+            }
+            (Value::Record(a), Value::Record(b)) => {
+                let (a, b) = (a.borrow(), b.borrow());
+                a.name == b.name && a.fields.iter().zip(b.fields.values()).all(|((_, x), y)| x.equals_nested(y, open))
+            }
+            (a, b) => compare_numbers(a, b) == Some(Ordering::Equal),
+        };
+        if pair {
+            open.pop();
+        }
+        equal
+    }
+
+    /// Ordinamento tra numeri, tra stringhe (per code point, o secondo la lingua con `collate`)
+    /// e tra array (lessicografico). Gli altri confronti, incluso `null`, sono errori di tipo.
+    pub fn compare(&self, other: &Value, collate: bool) -> Result<Ordering, String> {
+        self.compare_nested(other, collate, &mut Vec::new())
+    }
+
+    /// `open` contiene le coppie di array in corso di confronto: ritrovarne una significa che
+    /// l'ordinamento non termina mai, ed è un errore.
+    fn compare_nested(&self, other: &Value, collate: bool, open: &mut Vec<(*const (), *const ())>) -> Result<Ordering, String> {
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => {
+                let folded = if collate { fold(a).cmp(&fold(b)) } else { Ordering::Equal };
+                Ok(folded.then_with(|| a.cmp(b)))
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                if Rc::ptr_eq(a, b) {
+                    return Ok(Ordering::Equal);
+                }
+                let pair = (Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const ());
+                if open.contains(&pair) {
+                    return Err("TYPE ERROR: Cannot compare cyclic values (array containing itself)".to_string());
+                }
+                open.push(pair);
+                let ordering = compare_arrays(&a.borrow(), &b.borrow(), collate, open);
+                open.pop();
+                ordering
+            }
+            (a, b) => compare_numbers(a, b).ok_or_else(|| {
+                format!("TYPE ERROR: Cannot compare {} with {}", a.type_name(), b.type_name())
+            }),
         }
     }
-}
 
-impl Value {
     pub fn is_truthy(&self) -> bool {
         match self {
             Value::Null => false,
             Value::Boolean(b) => *b,
             Value::Integer(i) => *i != 0,
+            Value::BigInt(i) => !i.is_zero(),
             Value::Float(f) => *f != 0.0,
+            Value::Decimal(d) => !d.is_zero(),
             Value::String(s) => !s.is_empty(),
-            Value::Array(a) => !a.is_empty(),
-            Value::Map(m) => !m.is_empty(),
+            Value::Array(a) => !a.borrow().is_empty(),
+            Value::Map(m) => !m.borrow().is_empty(),
+            Value::Set(s) => !s.is_empty(),
+            Value::Record(_) | Value::Iterator(_) | Value::Channel(_) | Value::Task(_) => true,
+        }
+    }
+
+
+}
+
+/// Ordinamento lessicografico: il primo elemento diverso decide, altrimenti l'array più corto viene prima.
+fn compare_arrays(a: &[Value], b: &[Value], collate: bool, open: &mut Vec<(*const (), *const ())>) -> Result<Ordering, String> {
+    for (x, y) in a.iter().zip(b.iter()) {
+        match x.compare_nested(y, collate, open)? {
+            Ordering::Equal => continue,
+            ordering => return Ok(ordering),
+        }
+    }
+    Ok(a.len().cmp(&b.len()))
+}
+
+/// Confronto tra due numeri di qualsiasi tipo numerico, `None` se uno dei due non è un numero (o è NaN).
+fn compare_numbers(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Integer(x), Value::Integer(y)) => Some(x.cmp(y)),
+        (Value::Integer(_) | Value::BigInt(_), Value::Integer(_) | Value::BigInt(_)) => {
+            let big = |v: &Value| match v {
+                Value::Integer(i) => BigInt::from(*i),
+                Value::BigInt(i) => i.clone(),
+                _ => BigInt::zero(),
+            };
+            Some(big(a).cmp(&big(b)))
+        }
+        (Value::Decimal(_), _) | (_, Value::Decimal(_)) if b.as_f64().is_some() && a.as_f64().is_some() => {
+            match (a.as_decimal(), b.as_decimal()) {
+                (Some(x), Some(y)) => Some(x.cmp(&y)),
+                _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+            }
+        }
+        _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+    }
+}
+
+/// Posizioni degli elementi di un insieme raggruppate per impronta (`Value::set_hash`): cercare
+/// un valore lo confronta solo con gli elementi della stessa impronta.
+pub struct SetIndex {
+    // Ultimo elemento inserito per ogni impronta; `previous[i]` è quello precedente con la stessa impronta di `i`
+    last: HashMap<u64, usize>,
+    previous: Vec<Option<usize>>,
+}
+
+impl SetIndex {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { last: HashMap::with_capacity(capacity), previous: Vec::with_capacity(capacity) }
+    }
+
+    pub fn new(items: &[Value]) -> Self {
+        let mut index = Self::with_capacity(items.len());
+        for item in items {
+            index.push(item.set_hash());
         }
+        index
+    }
+
+    /// Registra l'elemento successivo, con la sua impronta.
+    pub fn push(&mut self, hash: u64) {
+        let previous = self.last.insert(hash, self.previous.len());
+        self.previous.push(previous);
     }
 
+    /// Posizioni degli elementi che possono essere uguali a un valore con impronta `hash`.
+    pub fn candidates(&self, hash: u64) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.last.get(&hash).copied(), |&idx| self.previous[idx])
+    }
 
-}
\ No newline at end of file
+    pub fn contains(&self, items: &[Value], value: &Value) -> bool {
+        self.candidates(value.set_hash()).any(|idx| items[idx].equals(value))
+    }
+}
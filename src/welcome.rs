@@ -1,6 +1,11 @@
+use crate::engine::translate::KeywordConflict;
+use icu_properties::CodePointMapData;
+use icu_properties::props::BidiClass;
+
 const COLOR_RESET: &str = "\x1b[0m";
 const COLOR_GREEN: &str = "\x1b[32m";
 const COLOR_RED: &str = "\x1b[31m";
+const COLOR_YELLOW: &str = "\x1b[33m";
 const COLOR_CYAN: &str = "\x1b[36m";
 
 pub fn show_banner() {
@@ -17,10 +22,16 @@ pub fn show_banner() {
 pub fn show_usage() {
     println!("Usage:");
     println!("  cargo run -- build <file.ns>           Compile and run a NodeStract file");
+    println!("  cargo run -- vocab <language>          List the keywords of a language (preferred spelling first)");
+    println!("  cargo run -- vocab --conflicts         List words with different meanings across languages");
+    println!("      --lang-pack <path>                 Load extra language packs (JSON file or folder)");
+    println!("      --machine-output                   Print values as true/false/null with a decimal point");
+    println!("      --collate                          Order strings ignoring case and accents");
     println!("  cargo run -- version                   Show version information");
     println!("  cargo run --example lessons            Launch the interactive lessons mode");
     println!("  cargo test                             Run the unit and integration test suite");
-    println!("\nLanguages supported concurrently: en, it, es, fr, de, pt, ro");
+    println!("\nLanguages supported concurrently: en, it, es, fr, de, pt, ro, el, ru, ar");
+    println!("Custom language packs are also loaded from ~/.nodestract/languages/*.json");
 }
 
 pub fn show_welcome() {
@@ -32,8 +43,67 @@ pub fn show_version() {
     println!("Node Stract Compiler (NSC) v{} - HawkStract Ecosystem", env!("CARGO_PKG_VERSION"));
 }
 
+pub fn show_conflicts(conflicts: &[KeywordConflict]) {
+    if conflicts.is_empty() {
+        show_success("No cross-language keyword conflicts found.");
+        return;
+    }
+    println!("Cross-language keyword conflicts: {}", conflicts.len());
+    for conflict in conflicts {
+        println!(
+            "  {}'{}'{}: '{}' ({}) <-> '{}' ({})",
+            COLOR_YELLOW, conflict.word, COLOR_RESET,
+            conflict.first_keyword, conflict.first_language,
+            conflict.second_keyword, conflict.second_language
+        );
+    }
+}
+
+pub fn show_vocabulary(language: &str, entries: &[(&str, &[String])]) {
+    println!("Keywords of '{}' (preferred spelling first):", language);
+    for (canonical, spellings) in entries {
+        let synonyms = if spellings.len() > 1 {
+            format!(" (also: {})", spellings[1..].join(", "))
+        } else {
+            String::new()
+        };
+        println!("  {:<12} {}{}{}{}", canonical, COLOR_CYAN, spellings[0], COLOR_RESET, synonyms);
+    }
+}
+
 pub fn show_error(msg: &str) {
-    println!("{}Error: {}{}", COLOR_RED, msg, COLOR_RESET);
+    println!("{}Error: {}{}", COLOR_RED, isolate_rtl(msg), COLOR_RESET);
+}
+
+pub fn show_warning(msg: &str) {
+    println!("{}{}{}", COLOR_YELLOW, isolate_rtl(msg), COLOR_RESET);
+}
+
+/// Racchiude le parole scritte da destra a sinistra (es. identificatori arabi) tra i caratteri
+/// di isolamento bidirezionale, così che il terminale non riordini il resto del messaggio.
+fn isolate_rtl(msg: &str) -> String {
+    let bidi = CodePointMapData::<BidiClass>::new();
+    let is_rtl = |c: char| matches!(bidi.get(c), BidiClass::RightToLeft | BidiClass::ArabicLetter);
+
+    let mut result = String::new();
+    let mut word = String::new();
+    for c in msg.chars().chain(std::iter::once(' ')) {
+        if !c.is_whitespace() && !c.is_ascii_punctuation() {
+            word.push(c);
+            continue;
+        }
+        if word.chars().any(is_rtl) {
+            result.push('\u{2068}');
+            result.push_str(&word);
+            result.push('\u{2069}');
+        } else {
+            result.push_str(&word);
+        }
+        word.clear();
+        result.push(c);
+    }
+    result.pop();
+    result
 }
 
 pub fn show_success(msg: &str) {
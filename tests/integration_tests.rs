@@ -9,6 +9,7 @@ use std::sync::Once;
 static START_SERVER: Once = Once::new();
 static RETRY_COUNT: AtomicU32 = AtomicU32::new(0);
 
+#[allow(clippy::manual_flatten)]
 fn start_mock_server() {
     START_SERVER.call_once(|| {
         thread::spawn(|| {
@@ -75,7 +76,7 @@ fn run_one_test(path: &str) {
     }
 
     let output = Command::new("cargo")
-        .args(&["run", "--quiet", "--", "build", path])
+        .args(["run", "--quiet", "--", "build", path])
         .output()
         .expect("Failed to execute cargo run");
 
@@ -96,6 +97,20 @@ fn run_one_test(path: &str) {
             full_output
         );
     }
+
+    // I sorgenti possono dichiarare gli errori di compilazione attesi con "// EXPECT ERROR: <testo>"
+    let source = std::fs::read_to_string(path).expect("Failed to read test source");
+    for line in source.lines() {
+        if let Some(expected) = line.trim().strip_prefix("// EXPECT ERROR:") {
+            let expected = expected.trim();
+            if !full_output.contains(expected) {
+                panic!(
+                    "Expected error '{}' not reported.\n--- OUTPUT ---\n{}\n--------------",
+                    expected, full_output
+                );
+            }
+        }
+    }
 }
 
 include!(concat!(env!("OUT_DIR"), "/generated_tests.rs"));